@@ -0,0 +1,2 @@
+#[cfg(feature = "clap")]
+pub mod clap;
@@ -0,0 +1,84 @@
+use crate::Fli;
+
+/// Converts a `clap::Command` into a `Fli` app with the same options and commands, to migrate from clap step by step
+///
+/// the options and commands get no-op callbacks, add yours with `Fli::option` on the same name to replace them.
+/// Positional arguments have no counterpart and are left out, read them with `Fli::get_arg_at`
+/// # Arguments
+/// * `command` - The clap command
+///
+/// # Example
+/// ```
+/// let command = clap::Command::new("myapp")
+///     .about("a sample app")
+///     .arg(clap::Arg::new("name").short('n').long("name"))
+///     .subcommand(clap::Command::new("greet").about("Greet someone"));
+/// let app = fli::compat::clap::from_clap(&command);
+/// assert_eq!(app.get_app_name(), "myapp");
+/// ```
+///
+/// # Returns
+/// * `Fli` - The app
+pub fn from_clap(command: &::clap::Command) -> Fli {
+    let mut command = command.clone();
+    command.build();
+    let mut app = Fli::init(command.get_name(), &about(&command));
+    if let Some(version) = command.get_version() {
+        app.set_version(version);
+    }
+    add_clap_args(&mut app, &command);
+    app
+}
+
+/// Adds the options and commands of a clap command to a `Fli` app or command
+fn add_clap_args(app: &mut Fli, command: &::clap::Command) {
+    for arg in command.get_arguments() {
+        // fli has its own help and version options
+        if arg.is_positional() || ["help", "version"].contains(&arg.get_id().as_str()) {
+            continue;
+        }
+        let description = arg
+            .get_help()
+            .map(|help| help.to_string())
+            .unwrap_or_default();
+        app.option(&option_key(arg), &description, |_x| {});
+    }
+    for subcommand in command.get_subcommands() {
+        let mut subcommand = subcommand.clone();
+        subcommand.build();
+        let fli_command = app.command(subcommand.get_name(), &about(&subcommand));
+        add_clap_args(fli_command, &subcommand);
+    }
+}
+
+/// Gets the `-n --name, <>` key of a clap option
+fn option_key(arg: &::clap::Arg) -> String {
+    let long = match arg.get_long() {
+        Some(long) => format!("--{long}"),
+        None => format!("--{}", arg.get_id()),
+    };
+    let mut key = match arg.get_short() {
+        Some(short) => format!("-{short} {long}"),
+        None => long,
+    };
+    let takes_values = arg.get_action().takes_values();
+    if let (true, Some(range)) = (takes_values, arg.get_num_args()) {
+        let many = range.max_values() > 1 || matches!(arg.get_action(), ::clap::ArgAction::Append);
+        let param_type = match (range.min_values() == 0, many) {
+            (true, false) => "[]",
+            (false, false) => "<>",
+            (true, true) => "[...]",
+            (false, true) => "<...>",
+        };
+        key = format!("{key}, {param_type}");
+    }
+    key
+}
+
+/// Gets the description of a clap command
+fn about(command: &::clap::Command) -> String {
+    command
+        .get_about()
+        .map(|about| about.to_string())
+        .unwrap_or_default()
+}
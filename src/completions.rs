@@ -0,0 +1,203 @@
+use crate::{CliSpec, Fli, OptionInfo};
+
+/// Writes the completion spec of an app for [carapace](https://carapace.sh), a YAML file
+/// to put in `~/.config/carapace/specs/<app>.yaml`, the values of options with a `Fli::value_hint` are completed too
+/// # Arguments
+/// * `app` - The app, its commands and options are read with `Fli::to_spec`
+///
+/// # Example
+/// ```
+/// let mut app = fli::Fli::init("myapp", "a sample app");
+/// app.option("-n --name, <>", "Your name", |_x| {});
+/// let spec = fli::completions::carapace_spec(&app);
+/// assert!(spec.contains("  \"-n, --name=\": \"Your name\""));
+/// ```
+///
+/// # Returns
+/// * `String` - The YAML spec
+pub fn carapace_spec(app: &Fli) -> String {
+    let mut yaml = String::new();
+    write_carapace_command(&mut yaml, &app.to_spec(), "");
+    yaml
+}
+
+fn write_carapace_command(yaml: &mut String, spec: &CliSpec, indent: &str) {
+    yaml.push_str(&format!("{indent}name: {}\n", quote(&spec.name)));
+    yaml.push_str(&format!(
+        "{indent}description: {}\n",
+        quote(&spec.description)
+    ));
+    if !spec.options.is_empty() {
+        yaml.push_str(&format!("{indent}flags:\n"));
+        for option in &spec.options {
+            // `=` for a value, `?` for an optional one
+            let value = match option.param.as_str() {
+                "" => "",
+                param if param.starts_with('[') => "?",
+                _ => "=",
+            };
+            let flag = format!("{}{value}", option_names(option).join(", "));
+            yaml.push_str(&format!(
+                "{indent}  {}: {}\n",
+                quote(&flag),
+                quote(&option.description)
+            ));
+        }
+        let completions: Vec<(&str, Vec<String>)> = spec
+            .options
+            .iter()
+            .filter_map(|option| {
+                let values = match option.value_hint.as_str() {
+                    "file" => vec![quote("$files")],
+                    "dir" => vec![quote("$directories")],
+                    "hostname" => vec![quote("$carapace.net.Hosts")],
+                    "choice" => option.choices.iter().map(|choice| quote(choice)).collect(),
+                    _ => return None,
+                };
+                Some((option.long.trim_start_matches('-'), values))
+            })
+            .collect();
+        if !completions.is_empty() {
+            yaml.push_str(&format!("{indent}completion:\n{indent}  flag:\n"));
+            for (name, values) in completions {
+                yaml.push_str(&format!("{indent}    {name}: [{}]\n", values.join(", ")));
+            }
+        }
+    }
+    if !spec.commands.is_empty() {
+        yaml.push_str(&format!("{indent}commands:\n"));
+        for command in &spec.commands {
+            let mut command_yaml = String::new();
+            write_carapace_command(&mut command_yaml, command, &format!("{indent}    "));
+            // the first line of the command starts the list item
+            yaml.push_str(&format!(
+                "{indent}  - {}",
+                &command_yaml[indent.len() + 4..]
+            ));
+        }
+    }
+}
+
+/// Writes the completion spec of an app for [Fig](https://fig.io) and its successors (Amazon Q, Kiro),
+/// a TypeScript file to put in the `src` directory of the autocomplete repository, suggesting the values set with `Fli::value_hint`
+/// # Arguments
+/// * `app` - The app, its commands, options and positional args are read with `Fli::to_spec`
+///
+/// # Example
+/// ```
+/// let mut app = fli::Fli::init("myapp", "a sample app");
+/// app.option("-n --name, <>", "Your name", |_x| {});
+/// let spec = fli::completions::fig_spec(&app);
+/// assert!(spec.starts_with("const completionSpec: Fig.Spec = {"));
+/// assert!(spec.contains("name: [\"-n\", \"--name\"]"));
+/// ```
+///
+/// # Returns
+/// * `String` - The TypeScript spec
+pub fn fig_spec(app: &Fli) -> String {
+    let mut ts = String::from("const completionSpec: Fig.Spec = ");
+    write_fig_command(&mut ts, &app.to_spec(), "");
+    ts.push_str(";\n\nexport default completionSpec;\n");
+    ts
+}
+
+fn write_fig_command(ts: &mut String, spec: &CliSpec, indent: &str) {
+    ts.push_str("{\n");
+    ts.push_str(&format!("{indent}  name: {},\n", quote(&spec.name)));
+    ts.push_str(&format!(
+        "{indent}  description: {},\n",
+        quote(&spec.description)
+    ));
+    if !spec.commands.is_empty() {
+        ts.push_str(&format!("{indent}  subcommands: [\n"));
+        for command in &spec.commands {
+            ts.push_str(&format!("{indent}    "));
+            write_fig_command(ts, command, &format!("{indent}    "));
+            ts.push_str(",\n");
+        }
+        ts.push_str(&format!("{indent}  ],\n"));
+    }
+    if !spec.options.is_empty() {
+        ts.push_str(&format!("{indent}  options: [\n"));
+        for option in &spec.options {
+            let names: Vec<String> = option_names(option)
+                .iter()
+                .map(|name| quote(name))
+                .collect();
+            ts.push_str(&format!("{indent}    {{\n"));
+            ts.push_str(&format!("{indent}      name: [{}],\n", names.join(", ")));
+            ts.push_str(&format!(
+                "{indent}      description: {},\n",
+                quote(&option.description)
+            ));
+            if !option.param.is_empty() {
+                let name = option.long.trim_start_matches('-');
+                ts.push_str(&format!(
+                    "{indent}      args: {},\n",
+                    fig_arg(name, &option.param, &option.value_hint, &option.choices)
+                ));
+            }
+            ts.push_str(&format!("{indent}    }},\n"));
+        }
+        ts.push_str(&format!("{indent}  ],\n"));
+    }
+    if !spec.arguments.is_empty() {
+        ts.push_str(&format!("{indent}  args: [\n"));
+        for argument in &spec.arguments {
+            ts.push_str(&format!(
+                "{indent}    {},\n",
+                fig_arg(&argument.name, &argument.param, "", &[])
+            ));
+        }
+        ts.push_str(&format!("{indent}  ],\n"));
+    }
+    ts.push_str(&format!("{indent}}}"));
+}
+
+/// Writes a Fig arg from its name, param type template and value hint
+fn fig_arg(name: &str, param: &str, value_hint: &str, choices: &[String]) -> String {
+    let mut arg = format!("{{ name: {}", quote(name));
+    if param.starts_with('[') {
+        arg.push_str(", isOptional: true");
+    }
+    if param.contains("...") {
+        arg.push_str(", isVariadic: true");
+    }
+    match value_hint {
+        "file" => arg.push_str(", template: \"filepaths\""),
+        "dir" => arg.push_str(", template: \"folders\""),
+        "choice" => {
+            let choices: Vec<String> = choices.iter().map(|choice| quote(choice)).collect();
+            arg.push_str(&format!(", suggestions: [{}]", choices.join(", ")));
+        }
+        _ => {}
+    }
+    arg + " }"
+}
+
+/// Gets the names of an option, the short one first
+fn option_names(option: &OptionInfo) -> Vec<&str> {
+    option
+        .short
+        .iter()
+        .chain([&option.long])
+        .map(String::as_str)
+        .collect()
+}
+
+/// Quotes a string for YAML and TypeScript, both reading JSON strings
+fn quote(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
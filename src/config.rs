@@ -0,0 +1,186 @@
+use std::{collections::HashMap, fs, io, path::Path};
+
+/// The option values of a command in a config file and the sections of its commands
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Section {
+    /// The values by long option name without the dashes, flags set to `false` hold `"false"`
+    pub values: HashMap<String, Vec<String>>,
+    /// The sections of the commands by command name
+    pub sections: HashMap<String, Section>,
+}
+
+impl Section {
+    /// Gets the section of a command from its path of command names, this section for an empty path
+    pub fn get(&self, path: &[String]) -> Option<&Section> {
+        match path.split_first() {
+            Some((name, rest)) => self.sections.get(name)?.get(rest),
+            None => Some(self),
+        }
+    }
+
+    /// Builds a section from the entries of a table
+    fn from_table(entries: Vec<(String, Node)>) -> Self {
+        let mut section = Self::default();
+        for (key, node) in entries {
+            match node {
+                Node::Table(entries) => {
+                    section.sections.insert(key, Self::from_table(entries));
+                }
+                #[cfg(any(feature = "json", feature = "yaml"))]
+                Node::Null => {}
+                Node::Scalar(value) => {
+                    section.values.insert(key, vec![value]);
+                }
+                Node::List(nodes) => {
+                    let values = nodes
+                        .into_iter()
+                        .filter_map(|node| match node {
+                            Node::Scalar(value) => Some(value),
+                            _ => None,
+                        })
+                        .collect();
+                    section.values.insert(key, values);
+                }
+            }
+        }
+        section
+    }
+}
+
+/// A value of a config file, whatever its format
+#[cfg_attr(
+    not(any(feature = "toml", feature = "json", feature = "yaml")),
+    allow(dead_code)
+)]
+enum Node {
+    /// Only json and yaml have a null, toml leaves the key out
+    #[cfg(any(feature = "json", feature = "yaml"))]
+    Null,
+    /// Strings, numbers and booleans, as they would be passed on the command line
+    Scalar(String),
+    List(Vec<Node>),
+    Table(Vec<(String, Node)>),
+}
+
+/// Loads a config file, its format picked from the extension (`.toml`, `.json`, `.yaml` or `.yml`)
+/// # Arguments
+/// * `path` - The path of the file
+/// * `required` - Whether a missing file is an error, it is skipped otherwise
+///
+/// # Returns
+/// * `Result<Option<Section>, String>` - The top section, none if the file is missing and not required
+pub(crate) fn load(path: &str, required: bool) -> Result<Option<Section>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) if !required && error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(format!("can not read {path} : {error}")),
+    };
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default();
+    let node = match extension {
+        "toml" => parse_toml(&content),
+        "json" => parse_json(&content),
+        "yaml" | "yml" => parse_yaml(&content),
+        _ => Err(format!(
+            "unknown config format '.{extension}', use .toml, .json or .yaml"
+        )),
+    };
+    match node.map_err(|message| format!("{path} : {message}"))? {
+        Node::Table(entries) => Ok(Some(Section::from_table(entries))),
+        _ => Err(format!("{path} : expected a table of options")),
+    }
+}
+
+/// The error for a format whose feature is off
+#[cfg(not(all(feature = "toml", feature = "json", feature = "yaml")))]
+fn needs_feature(feature: &str) -> String {
+    format!("reading .{feature} files needs the `{feature}` feature of fli")
+}
+
+#[cfg(feature = "toml")]
+fn parse_toml(content: &str) -> Result<Node, String> {
+    fn convert(value: toml::Value) -> Node {
+        match value {
+            toml::Value::Array(values) => Node::List(values.into_iter().map(convert).collect()),
+            toml::Value::Table(table) => Node::Table(
+                table
+                    .into_iter()
+                    .map(|(key, value)| (key, convert(value)))
+                    .collect(),
+            ),
+            toml::Value::String(value) => Node::Scalar(value),
+            value => Node::Scalar(value.to_string()),
+        }
+    }
+    let table: toml::Table = content
+        .parse()
+        .map_err(|error: toml::de::Error| error.message().to_string())?;
+    Ok(convert(toml::Value::Table(table)))
+}
+
+#[cfg(not(feature = "toml"))]
+fn parse_toml(_content: &str) -> Result<Node, String> {
+    Err(needs_feature("toml"))
+}
+
+#[cfg(feature = "json")]
+fn parse_json(content: &str) -> Result<Node, String> {
+    fn convert(value: serde_json::Value) -> Node {
+        match value {
+            serde_json::Value::Null => Node::Null,
+            serde_json::Value::Array(values) => {
+                Node::List(values.into_iter().map(convert).collect())
+            }
+            serde_json::Value::Object(map) => Node::Table(
+                map.into_iter()
+                    .map(|(key, value)| (key, convert(value)))
+                    .collect(),
+            ),
+            serde_json::Value::String(value) => Node::Scalar(value),
+            value => Node::Scalar(value.to_string()),
+        }
+    }
+    let value: serde_json::Value =
+        serde_json::from_str(content).map_err(|error| error.to_string())?;
+    Ok(convert(value))
+}
+
+#[cfg(not(feature = "json"))]
+fn parse_json(_content: &str) -> Result<Node, String> {
+    Err(needs_feature("json"))
+}
+
+#[cfg(feature = "yaml")]
+fn parse_yaml(content: &str) -> Result<Node, String> {
+    fn convert(value: serde_yaml::Value) -> Node {
+        match value {
+            serde_yaml::Value::Null => Node::Null,
+            serde_yaml::Value::Bool(value) => Node::Scalar(value.to_string()),
+            serde_yaml::Value::Number(value) => Node::Scalar(value.to_string()),
+            serde_yaml::Value::String(value) => Node::Scalar(value),
+            serde_yaml::Value::Sequence(values) => {
+                Node::List(values.into_iter().map(convert).collect())
+            }
+            serde_yaml::Value::Mapping(mapping) => Node::Table(
+                mapping
+                    .into_iter()
+                    .filter_map(|(key, value)| match key {
+                        serde_yaml::Value::String(key) => Some((key, convert(value))),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            serde_yaml::Value::Tagged(tagged) => convert(tagged.value),
+        }
+    }
+    let value: serde_yaml::Value =
+        serde_yaml::from_str(content).map_err(|error| error.to_string())?;
+    Ok(convert(value))
+}
+
+#[cfg(not(feature = "yaml"))]
+fn parse_yaml(_content: &str) -> Result<Node, String> {
+    Err(needs_feature("yaml"))
+}
@@ -0,0 +1,1293 @@
+use std::{
+    cell::{Cell, RefCell},
+    env, fmt,
+    io::{self, IsTerminal, Write},
+    process::{Command, Stdio},
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+pub use colored::Color;
+use colored::Colorize;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::value::FliValue;
+
+thread_local! {
+    /// The writer normal output goes to, stdout when not set
+    static OUTPUT: RefCell<Option<Box<dyn Write>>> = RefCell::new(None);
+    /// The writer error output goes to, stderr when not set
+    static ERROR_OUTPUT: RefCell<Option<Box<dyn Write>>> = RefCell::new(None);
+    /// The widest output can get, also used when the terminal width is unknown
+    static MAX_WIDTH: Cell<usize> = const { Cell::new(DEFAULT_MAX_WIDTH) };
+    /// Whether warnings and other non essential output are hidden
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+    /// How much output is wanted, 0 by default, raised by `--verbose` and lowered by `--quiet`
+    static VERBOSITY: Cell<i8> = const { Cell::new(0) };
+    /// The colors and symbols of the output
+    static THEME: RefCell<Theme> = RefCell::new(Theme::default());
+    /// Whether only ASCII symbols are used, detected from the environment when not set
+    static ASCII_OUTPUT: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+/// The symbols fli output uses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbols {
+    /// Before each item of a list, like the `Did you mean:` suggestions
+    pub bullet: &'static str,
+    /// Before success messages
+    pub success: &'static str,
+    /// Before error messages
+    pub error: &'static str,
+    /// Before warnings
+    pub warning: &'static str,
+    /// Before information messages
+    pub info: &'static str,
+    /// Pointing from one thing to another
+    pub arrow: &'static str,
+    /// Ending text that was cut
+    pub ellipsis: &'static str,
+    /// The frames of the `Spinner` animation
+    pub spinner: &'static [&'static str],
+}
+
+impl Symbols {
+    /// The unicode symbols, the default
+    pub fn unicode() -> Self {
+        Self {
+            bullet: "•",
+            success: "✓",
+            error: "✗",
+            warning: "⚠",
+            info: "ℹ",
+            arrow: "→",
+            ellipsis: "…",
+            spinner: &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+        }
+    }
+
+    /// The ASCII symbols, used instead of the ones of the theme when `is_ascii_output`
+    pub fn ascii() -> Self {
+        Self {
+            bullet: "*",
+            success: "+",
+            error: "x",
+            warning: "!",
+            info: "i",
+            arrow: "->",
+            ellipsis: "...",
+            spinner: &["|", "/", "-", "\\"],
+        }
+    }
+}
+
+impl Default for Symbols {
+    fn default() -> Self {
+        Self::unicode()
+    }
+}
+
+/// The colors and symbols of fli output, set with `set_theme` or `Fli::set_theme`
+///
+/// # Example
+/// ```
+/// use fli::display::{set_theme, Color, Theme};
+///
+/// set_theme(Theme {
+///     header: Color::Magenta,
+///     option: Color::Cyan,
+///     ..Theme::default()
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    /// Errors, the error banner and suggestions
+    pub error: Color,
+    /// Warnings
+    pub warning: Color,
+    /// Success messages
+    pub success: Color,
+    /// Information messages
+    pub info: Color,
+    /// Section headers of the help screen like `Options:`
+    pub header: Color,
+    /// Labels of the help screen like `Name:` and `Usage:`
+    pub label: Color,
+    /// Option and command names in tables
+    pub option: Color,
+    /// Short option names in tables
+    pub short: Color,
+    /// Descriptions in tables
+    pub description: Color,
+    /// The symbols to use
+    pub symbols: Symbols,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            error: Color::Red,
+            warning: Color::Yellow,
+            success: Color::Green,
+            info: Color::Cyan,
+            header: Color::Blue,
+            label: Color::Green,
+            option: Color::Blue,
+            short: Color::Green,
+            description: Color::Yellow,
+            symbols: Symbols::default(),
+        }
+    }
+}
+
+/// Sets the colors and symbols of fli output on the current thread
+pub fn set_theme(theme: Theme) {
+    THEME.with(|current| *current.borrow_mut() = theme);
+}
+
+/// Gets the colors and symbols of fli output on the current thread,
+/// with the ASCII symbols when `is_ascii_output`
+pub fn theme() -> Theme {
+    let mut theme = THEME.with(|theme| theme.borrow().clone());
+    if is_ascii_output() {
+        theme.symbols = Symbols::ascii();
+    }
+    theme
+}
+
+/// Sets the theme, when there is one, until the returned guard is dropped
+pub(crate) fn scoped_theme(theme: Option<Theme>) -> RestoreTheme {
+    RestoreTheme(theme.map(|theme| THEME.with(|current| current.replace(theme))))
+}
+
+/// Puts back the theme replaced by `scoped_theme`
+pub(crate) struct RestoreTheme(Option<Theme>);
+
+impl Drop for RestoreTheme {
+    fn drop(&mut self) {
+        if let Some(theme) = self.0.take() {
+            set_theme(theme);
+        }
+    }
+}
+
+/// Makes fli output only use ASCII symbols on the current thread, for terminals and logs that garble unicode
+///
+/// When not set it is detected from the environment, see `is_ascii_output`
+pub fn set_ascii_output(ascii_output: bool) {
+    ASCII_OUTPUT.with(|c| c.set(Some(ascii_output)));
+}
+
+/// Checks if fli output only uses ASCII symbols
+///
+/// Unless set with `set_ascii_output` it does when `TERM` is `dumb`, the locale (`LC_ALL`, `LC_CTYPE` or `LANG`)
+/// is not UTF-8, or on Windows consoles other than Windows Terminal
+pub fn is_ascii_output() -> bool {
+    ASCII_OUTPUT.with(Cell::get).unwrap_or_else(|| !supports_unicode())
+}
+
+/// Checks if the environment can show unicode symbols
+fn supports_unicode() -> bool {
+    let var = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+    if var("TERM").as_deref() == Some("dumb") {
+        return false;
+    }
+    if cfg!(windows) {
+        return var("WT_SESSION").is_some() || var("TERM_PROGRAM").is_some();
+    }
+    match var("LC_ALL").or_else(|| var("LC_CTYPE")).or_else(|| var("LANG")) {
+        Some(locale) => {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        }
+        None => true,
+    }
+}
+
+/// The default for `set_max_width`
+pub const DEFAULT_MAX_WIDTH: usize = 100;
+
+/// The narrowest a wrapped column gets, however small the terminal is
+const MIN_COLUMN_WIDTH: usize = 20;
+
+/// Replaces where the help screen and other output of fli is written to on the current thread
+/// # Arguments
+/// * `writer` - The writer to use instead of stdout
+///
+/// # Example
+/// ```no_run
+/// let file = std::fs::File::create("help.txt").unwrap();
+/// fli::display::set_output(Box::new(file));
+/// ```
+pub fn set_output(writer: Box<dyn Write>) {
+    OUTPUT.with(|output| *output.borrow_mut() = Some(writer));
+}
+
+/// Replaces where the error output of fli is written to on the current thread
+/// # Arguments
+/// * `writer` - The writer to use instead of stderr
+pub fn set_error_output(writer: Box<dyn Write>) {
+    ERROR_OUTPUT.with(|output| *output.borrow_mut() = Some(writer));
+}
+
+/// Sends output and error output on the current thread back to stdout and stderr
+pub fn reset_output() {
+    OUTPUT.with(|output| *output.borrow_mut() = None);
+    ERROR_OUTPUT.with(|output| *output.borrow_mut() = None);
+}
+
+/// Runs `f` while collecting everything fli writes on the current thread
+///
+/// # Example
+/// ```
+/// let (_, stdout, _stderr) = fli::display::capture(|| fli::fli_println!("Hello"));
+/// assert_eq!(stdout, "Hello\n");
+/// ```
+///
+/// # Returns
+/// * `(T, String, String)` - The result of `f`, the output and the error output
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, String, String) {
+    let stdout = SharedBuffer::default();
+    let stderr = SharedBuffer::default();
+    let _restore = RestoreOutput {
+        output: OUTPUT.with(|output| output.borrow_mut().replace(Box::new(stdout.clone()))),
+        error_output: ERROR_OUTPUT
+            .with(|output| output.borrow_mut().replace(Box::new(stderr.clone()))),
+    };
+    let value = f();
+    (value, stdout.contents(), stderr.contents())
+}
+
+/// When fli output should be colored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorPolicy {
+    /// Color when writing to a terminal and `NO_COLOR` is not set
+    #[default]
+    Auto,
+    /// Always color, even when piped
+    Always,
+    /// Never color
+    Never,
+}
+
+impl FliValue for ColorPolicy {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value.to_ascii_lowercase().as_str() {
+            "auto" => Ok(ColorPolicy::Auto),
+            "always" => Ok(ColorPolicy::Always),
+            "never" => Ok(ColorPolicy::Never),
+            _ => Err(format!("'{value}' is not one of auto, always or never")),
+        }
+    }
+
+    fn type_name() -> &'static str {
+        "color policy"
+    }
+
+    fn completion_hints() -> Vec<String> {
+        vec!["auto".to_string(), "always".to_string(), "never".to_string()]
+    }
+}
+
+/// Sets when fli output is colored, this is global to the process
+///
+/// with `ColorPolicy::Auto` output is colored only when stdout is a terminal that has not been
+/// replaced with `set_output`, `NO_COLOR` is unset or empty, or `CLICOLOR_FORCE` is set
+/// # Arguments
+/// * `policy` - The color policy to use
+///
+/// # Example
+/// ```
+/// use fli::display::{set_color_policy, ColorPolicy};
+///
+/// set_color_policy(ColorPolicy::Never);
+/// ```
+pub fn set_color_policy(policy: ColorPolicy) {
+    colored::control::set_override(should_colorize(policy));
+}
+
+/// Checks if output should be colored under the given policy
+pub fn should_colorize(policy: ColorPolicy) -> bool {
+    match policy {
+        ColorPolicy::Always => true,
+        ColorPolicy::Never => false,
+        ColorPolicy::Auto => {
+            let is_set = |name: &str| env::var_os(name).is_some_and(|value| !value.is_empty());
+            if is_set("NO_COLOR") {
+                return false;
+            }
+            if is_set("CLICOLOR_FORCE") {
+                return true;
+            }
+            is_output_terminal()
+        }
+    }
+}
+
+/// Sets the widest the help screen gets on the current thread, also used when the terminal width is unknown
+/// # Arguments
+/// * `width` - The max width in characters, `DEFAULT_MAX_WIDTH` by default
+pub fn set_max_width(width: usize) {
+    MAX_WIDTH.with(|max_width| max_width.set(width));
+}
+
+/// Gets the width output should fit in, the terminal width capped to the max width
+///
+/// the `COLUMNS` environment variable is used when set, and the max width when
+/// the output is not a terminal
+pub fn terminal_width() -> usize {
+    let max_width = MAX_WIDTH.with(Cell::get);
+    let columns = env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse::<usize>().ok())
+        .filter(|columns| *columns > 0);
+    let width = match columns {
+        Some(columns) => columns,
+        None if OUTPUT.with(|output| output.borrow().is_none()) => {
+            match terminal_size::terminal_size() {
+                Some((terminal_size::Width(width), _)) => width as usize,
+                None => max_width,
+            }
+        }
+        None => max_width,
+    };
+    width.min(max_width)
+}
+
+/// Gets the width left for a column that starts after `used` characters
+pub fn remaining_width(used: usize) -> usize {
+    terminal_width().saturating_sub(used).max(MIN_COLUMN_WIDTH)
+}
+
+/// Gets the height of the terminal, from the `LINES` environment variable or the terminal itself
+pub fn terminal_height() -> Option<usize> {
+    let lines = env::var("LINES")
+        .ok()
+        .and_then(|lines| lines.trim().parse::<usize>().ok())
+        .filter(|lines| *lines > 0);
+    lines.or_else(|| {
+        terminal_size::terminal_size().map(|(_, terminal_size::Height(height))| height as usize)
+    })
+}
+
+/// Shows text through `$PAGER` (or `less -R`) when it is taller than the terminal
+///
+/// nothing is shown when the output is not a terminal, the text fits or the pager can not be started
+///
+/// # Returns
+/// * `bool` - Whether the text was shown through the pager
+pub fn page(text: &str) -> bool {
+    if !is_output_terminal() {
+        return false;
+    }
+    match terminal_height() {
+        Some(height) if text.lines().count() >= height => {}
+        _ => return false,
+    }
+    let pager = env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| "less -R".to_string());
+    let mut parts = pager.split_whitespace();
+    let Some(program) = parts.next() else {
+        return false;
+    };
+    let Ok(mut child) = Command::new(program).args(parts).stdin(Stdio::piped()).spawn() else {
+        return false;
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // the user quitting the pager early closes the pipe, which is fine
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child.wait().is_ok()
+}
+
+/// Splits text into lines no wider than `width`, breaking on whitespace where possible
+/// # Arguments
+/// * `text` - The text to wrap
+/// * `width` - The max width of a line in characters
+///
+/// # Example
+/// ```
+/// let lines = fli::display::wrap_text("print help screen for the app", 12);
+/// assert_eq!(lines, vec!["print help", "screen for", "the app"]);
+/// ```
+///
+/// # Returns
+/// * `Vec<String>` - The wrapped lines, at least one
+pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines: Vec<String> = vec![];
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let mut word = word.to_string();
+        // break words that cannot fit on a line of their own
+        while word.width() > width {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            let split = split_at_width(&word, width);
+            lines.push(word[..split].to_string());
+            word = word[split..].to_string();
+        }
+        if word.is_empty() {
+            continue;
+        }
+        if line.is_empty() {
+            line = word;
+        } else if line.width() + 1 + word.width() <= width {
+            line.push(' ');
+            line.push_str(&word);
+        } else {
+            lines.push(std::mem::replace(&mut line, word));
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Gets the byte index where the text gets wider than `width` columns, at least one character in
+fn split_at_width(text: &str, width: usize) -> usize {
+    let mut used = 0;
+    for (index, c) in text.char_indices() {
+        used += c.width().unwrap_or(0);
+        if used > width && index > 0 {
+            return index;
+        }
+    }
+    text.len()
+}
+
+/// Cuts text to `width` columns, ending it with the ellipsis of the theme (`…`) when cut
+fn truncate_text(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let ellipsis = theme().symbols.ellipsis;
+    let split = split_at_width(text, width.saturating_sub(ellipsis.width()));
+    format!("{}{ellipsis}", &text[..split])
+}
+
+/// How the text of a table column is aligned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+/// What happens to cells wider than the max width of their column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    /// Wrap the text on more lines
+    #[default]
+    Wrap,
+    /// Cut the text, ending it with `…`
+    Truncate,
+}
+
+/// The borders of a table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TableStyle {
+    /// Columns separated by ` | `, like the help screen
+    #[default]
+    Plain,
+    /// Boxed with `+`, `-` and `|`, safe for logs and any terminal
+    Ascii,
+    /// A GitHub Markdown table, cells are kept on one line and not colored so it can be pasted as is
+    Markdown,
+    /// Columns separated by spaces only
+    Borderless,
+}
+
+impl TableStyle {
+    /// Gets what goes before, between and after the cells of a line
+    fn separators(&self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            TableStyle::Plain => ("", " | ", ""),
+            TableStyle::Ascii | TableStyle::Markdown => ("| ", " | ", " |"),
+            TableStyle::Borderless => ("", "  ", ""),
+        }
+    }
+}
+
+/// A table laid out on the width of the terminal, widths are measured in terminal columns so wide characters line up
+///
+/// the last column shrinks to fit the terminal width when the table is too wide
+///
+/// # Example
+/// ```
+/// use fli::display::{Align, Overflow, Table};
+/// # fli::display::set_ascii_output(false);
+///
+/// let mut table = Table::new(["Name", "Size", "Description"]);
+/// table
+///     .align(1, Align::Right)
+///     .max_width(2, 12)
+///     .overflow(2, Overflow::Truncate)
+///     .add_row(["notes.txt", "12K", "some notes to read later"])
+///     .add_row(["a.out", "1.2M", "a program"]);
+/// assert_eq!(
+///     table.render(),
+///     "Name      | Size | Description\nnotes.txt |  12K | some notes …\na.out     | 1.2M | a program\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    columns: Vec<TableColumn>,
+    indent: usize,
+    row_separators: bool,
+    style: TableStyle,
+}
+
+/// The settings of a table column
+#[derive(Debug, Clone, Default)]
+struct TableColumn {
+    align: Align,
+    max_width: Option<usize>,
+    overflow: Overflow,
+    color: Option<Color>,
+}
+
+impl Table {
+    /// Creates a table with the given headers, one per column
+    pub fn new<I: IntoIterator<Item = S>, S: Into<String>>(headers: I) -> Self {
+        let headers: Vec<String> = headers.into_iter().map(Into::into).collect();
+        Self {
+            columns: vec![TableColumn::default(); headers.len()],
+            headers,
+            ..Self::default()
+        }
+    }
+
+    /// Adds a row, missing cells are left empty and extra ones ignored
+    pub fn add_row<I: IntoIterator<Item = S>, S: Into<String>>(&mut self, row: I) -> &mut Self {
+        let mut row: Vec<String> = row.into_iter().map(Into::into).collect();
+        row.resize(self.headers.len(), String::new());
+        self.rows.push(row);
+        self
+    }
+
+    /// Sets how the text of a column is aligned, left by default
+    pub fn align(&mut self, column: usize, align: Align) -> &mut Self {
+        if let Some(column) = self.columns.get_mut(column) {
+            column.align = align;
+        }
+        self
+    }
+
+    /// Sets the widest a column gets in terminal columns
+    pub fn max_width(&mut self, column: usize, width: usize) -> &mut Self {
+        if let Some(column) = self.columns.get_mut(column) {
+            column.max_width = Some(width.max(1));
+        }
+        self
+    }
+
+    /// Sets what happens to cells wider than the max width of their column, wrapped by default
+    pub fn overflow(&mut self, column: usize, overflow: Overflow) -> &mut Self {
+        if let Some(column) = self.columns.get_mut(column) {
+            column.overflow = overflow;
+        }
+        self
+    }
+
+    /// Sets the color of a column, used when output is colored
+    pub fn color(&mut self, column: usize, color: Color) -> &mut Self {
+        if let Some(column) = self.columns.get_mut(column) {
+            column.color = Some(color);
+        }
+        self
+    }
+
+    /// Sets the number of spaces before each line
+    pub fn indent(&mut self, indent: usize) -> &mut Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Draws a line between rows, not in Markdown tables
+    pub fn row_separators(&mut self, data: bool) -> &mut Self {
+        self.row_separators = data;
+        self
+    }
+
+    /// Sets the borders of the table, `TableStyle::Plain` by default
+    pub fn style(&mut self, style: TableStyle) -> &mut Self {
+        self.style = style;
+        self
+    }
+
+    /// Gets the width of each column, shrinking the last one to fit the terminal when needed
+    fn column_widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                let natural = self
+                    .rows
+                    .iter()
+                    .map(|row| self.cell_text(&row[index]).width())
+                    .chain([self.cell_text(&self.headers[index]).width()])
+                    .max()
+                    .unwrap_or(0);
+                match (self.style, column.overflow, column.max_width) {
+                    // markdown cells can not span lines
+                    (TableStyle::Markdown, Overflow::Wrap, _) | (_, _, None) => natural,
+                    (_, _, Some(max)) => natural.min(max),
+                }
+            })
+            .collect();
+        if self.style == TableStyle::Markdown {
+            return widths;
+        }
+        let (left, middle, right) = self.style.separators();
+        if let Some((last, others)) = widths.split_last_mut() {
+            let used = self.indent
+                + left.len()
+                + others.iter().map(|width| width + middle.len()).sum::<usize>()
+                + right.len();
+            *last = (*last).min(remaining_width(used).max(1));
+        }
+        widths
+    }
+
+    /// Gets the text of a cell as drawn, with `|` escaped in Markdown tables
+    fn cell_text(&self, cell: &str) -> String {
+        match self.style {
+            TableStyle::Markdown => cell.replace('|', "\\|"),
+            _ => cell.to_string(),
+        }
+    }
+
+    /// Gets a line drawn between rows or around the table
+    fn rule(&self, widths: &[usize]) -> String {
+        let line = match self.style {
+            TableStyle::Plain => {
+                let parts: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+                parts.join("-+-")
+            }
+            TableStyle::Ascii => {
+                let parts: Vec<String> = widths.iter().map(|width| "-".repeat(width + 2)).collect();
+                format!("+{}+", parts.join("+"))
+            }
+            TableStyle::Markdown => {
+                let parts: Vec<String> = widths
+                    .iter()
+                    .zip(&self.columns)
+                    .map(|(width, column)| {
+                        let width = (*width).max(3);
+                        match column.align {
+                            Align::Left => "-".repeat(width),
+                            Align::Right => format!("{}:", "-".repeat(width - 1)),
+                            Align::Center => format!(":{}:", "-".repeat(width - 2)),
+                        }
+                    })
+                    .collect();
+                format!("| {} |", parts.join(" | "))
+            }
+            TableStyle::Borderless => {
+                let parts: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+                parts.join("  ")
+            }
+        };
+        format!("{}{}\n", " ".repeat(self.indent), line)
+    }
+
+    /// Renders the table
+    ///
+    /// # Returns
+    /// * `String` - The lines of the table, each ending with a newline
+    pub fn render(&self) -> String {
+        let mut widths = self.column_widths();
+        if self.style == TableStyle::Markdown {
+            // room for the `---` of the header line
+            widths.iter_mut().for_each(|width| *width = (*width).max(3));
+        }
+        let mut output = String::new();
+        let boxed = self.style == TableStyle::Ascii;
+        if boxed {
+            output.push_str(&self.rule(&widths));
+        }
+        self.render_row(&mut output, &self.headers, &widths, true);
+        if boxed || self.style == TableStyle::Markdown {
+            output.push_str(&self.rule(&widths));
+        }
+        for (index, row) in self.rows.iter().enumerate() {
+            if self.row_separators && index > 0 && self.style != TableStyle::Markdown {
+                output.push_str(&self.rule(&widths));
+            }
+            self.render_row(&mut output, row, &widths, false);
+        }
+        if boxed && !self.rows.is_empty() {
+            output.push_str(&self.rule(&widths));
+        }
+        output
+    }
+
+    /// Prints the table to the fli output
+    pub fn print(&self) {
+        print(format_args!("{}", self.render()));
+    }
+
+    fn render_row(&self, output: &mut String, row: &[String], widths: &[usize], header: bool) {
+        let markdown = self.style == TableStyle::Markdown;
+        let (left_border, separator, right_border) = self.style.separators();
+        let cells: Vec<Vec<String>> = row
+            .iter()
+            .map(|cell| self.cell_text(cell))
+            .zip(widths)
+            .zip(&self.columns)
+            .map(|((cell, width), column)| match column.overflow {
+                _ if cell.width() <= *width => vec![cell],
+                Overflow::Wrap if markdown => vec![cell],
+                Overflow::Wrap => wrap_text(&cell, *width),
+                Overflow::Truncate => vec![truncate_text(&cell, *width)],
+            })
+            .collect();
+        let height = cells.iter().map(Vec::len).max().unwrap_or(1);
+        for line in 0..height {
+            let mut parts: Vec<String> = vec![];
+            for (index, column) in self.columns.iter().enumerate() {
+                let text = cells[index].get(line).map(String::as_str).unwrap_or("");
+                let padding = widths[index].saturating_sub(text.width());
+                let (left, right) = match column.align {
+                    Align::Left => (0, padding),
+                    Align::Right => (padding, 0),
+                    Align::Center => (padding / 2, padding - padding / 2),
+                };
+                let mut styled = match column.color {
+                    Some(color) if !markdown => text.color(color),
+                    _ => text.normal(),
+                };
+                if header && !markdown {
+                    styled = styled.bold();
+                }
+                let is_last = index + 1 == self.columns.len();
+                let right = if is_last && right_border.is_empty() { 0 } else { right };
+                parts.push(format!("{}{}{}", " ".repeat(left), styled, " ".repeat(right)));
+            }
+            output.push_str(&format!(
+                "{}{}{}{}\n",
+                " ".repeat(self.indent),
+                left_border,
+                parts.join(separator),
+                right_border
+            ));
+        }
+    }
+}
+
+/// Hides warnings and other non essential output on the current thread
+/// # Arguments
+/// * `quiet` - Whether to hide them
+pub fn set_quiet(quiet: bool) {
+    QUIET.with(|q| q.set(quiet));
+}
+
+/// Checks if warnings and other non essential output are hidden
+///
+/// fli hides them while the callbacks run when the app has a `--quiet` option and it is passed
+pub fn is_quiet() -> bool {
+    QUIET.with(Cell::get)
+}
+
+/// Turns `set_quiet` on until the returned guard is dropped
+pub(crate) fn scoped_quiet(quiet: bool) -> RestoreQuiet {
+    RestoreQuiet(QUIET.with(|q| q.replace(q.get() || quiet)))
+}
+
+/// Puts back the quiet flag replaced by `scoped_quiet`, even if a callback exits
+pub(crate) struct RestoreQuiet(bool);
+
+impl Drop for RestoreQuiet {
+    fn drop(&mut self) {
+        QUIET.with(|q| q.set(self.0));
+    }
+}
+
+/// Sets how much output is wanted on the current thread, 0 by default
+///
+/// fli sets it while the callbacks run from the passed `--verbose` (+1 each), `--debug` (at least 2)
+/// and `--quiet` (-1 each) options the app has
+pub fn set_verbosity(verbosity: i8) {
+    VERBOSITY.with(|v| v.set(verbosity));
+}
+
+/// Gets how much output is wanted, see `set_verbosity`
+pub fn verbosity() -> i8 {
+    VERBOSITY.with(Cell::get)
+}
+
+/// Sets the verbosity until the returned guard is dropped
+pub(crate) fn scoped_verbosity(verbosity: i8) -> RestoreVerbosity {
+    RestoreVerbosity(VERBOSITY.with(|v| v.replace(verbosity)))
+}
+
+/// Puts back the verbosity replaced by `scoped_verbosity`, even if a callback exits
+pub(crate) struct RestoreVerbosity(i8);
+
+impl Drop for RestoreVerbosity {
+    fn drop(&mut self) {
+        VERBOSITY.with(|v| v.set(self.0));
+    }
+}
+
+/// Prints a debug message, to the error output when the verbosity is at least 2 (`-vv` or `--debug`)
+///
+/// with the `log` feature it goes through `log::debug!` instead, use the `fli_debug!` macro instead of calling this directly
+pub fn debug_print(args: fmt::Arguments) {
+    #[cfg(feature = "log")]
+    log::debug!(target: "fli", "{args}");
+    #[cfg(not(feature = "log"))]
+    if verbosity() >= 2 {
+        crate::fli_eprintln!("{} {args}", "debug:".dimmed());
+    }
+}
+
+/// Gets the `log` level filter of a verbosity, warnings and errors by default
+///
+/// | verbosity | filter |
+/// |-----------|--------|
+/// | -2 or less | `Off` |
+/// | -1 | `Error` |
+/// | 0 | `Warn` |
+/// | 1 | `Info` |
+/// | 2 | `Debug` |
+/// | 3 or more | `Trace` |
+#[cfg(feature = "log")]
+pub fn log_level_filter(verbosity: i8) -> log::LevelFilter {
+    match verbosity {
+        i8::MIN..=-2 => log::LevelFilter::Off,
+        -1 => log::LevelFilter::Error,
+        0 => log::LevelFilter::Warn,
+        1 => log::LevelFilter::Info,
+        2 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    }
+}
+
+/// A `log` logger writing records to the fli error output, like `warn: disk almost full`
+/// the level is read from the verbosity of the current thread, so apps running on other threads keep theirs
+#[cfg(feature = "log")]
+struct Logger;
+
+#[cfg(feature = "log")]
+impl log::Log for Logger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log_level_filter(verbosity())
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let theme = theme();
+        let label = format!("{}:", record.level().as_str().to_ascii_lowercase());
+        let label = match record.level() {
+            log::Level::Error => label.bold().color(theme.error),
+            log::Level::Warn => label.bold().color(theme.warning),
+            log::Level::Info => label.bold().color(theme.info),
+            log::Level::Debug | log::Level::Trace => label.dimmed(),
+        };
+        crate::fli_eprintln!("{label} {}", record.args());
+    }
+
+    fn flush(&self) {}
+}
+
+/// Installs a `log` logger writing to the fli error output, its level follows the verbosity flags while the callbacks run
+/// the global `log` max level is set to `Trace` once, the logger filters the records itself
+///
+/// # Returns
+/// * `Result<(), log::SetLoggerError>` - An error if a logger was already installed
+#[cfg(feature = "log")]
+pub fn init_logger() -> Result<(), log::SetLoggerError> {
+    static LOGGER: Logger = Logger;
+    log::set_logger(&LOGGER)?;
+    log::set_max_level(log::LevelFilter::Trace);
+    Ok(())
+}
+
+/// Prints a one line warning to the error output, unless quiet or the verbosity is below -1
+/// # Arguments
+/// * `message` - The warning
+pub fn print_warning(message: &str) {
+    if is_quiet() || verbosity() < -1 {
+        return;
+    }
+    let theme = theme();
+    let label = format!("{} {}", theme.symbols.warning, crate::messages::messages().warning_label);
+    crate::fli_eprintln!("{} {}", label.bold().color(theme.warning), message);
+}
+
+/// Prints a one line error to the error output
+/// # Arguments
+/// * `message` - The error
+pub fn print_error(message: &str) {
+    let theme = theme();
+    crate::fli_eprintln!("{} {}", theme.symbols.error.bold().color(theme.error), message);
+}
+
+/// Prints a one line success message to the output, unless quiet or the verbosity is below 0
+/// # Arguments
+/// * `message` - The message
+pub fn print_success(message: &str) {
+    if is_quiet() || verbosity() < 0 {
+        return;
+    }
+    let theme = theme();
+    crate::fli_println!("{} {}", theme.symbols.success.bold().color(theme.success), message);
+}
+
+/// Prints a one line information message to the output, unless quiet or the verbosity is below 0
+/// # Arguments
+/// * `message` - The message
+pub fn print_info(message: &str) {
+    if is_quiet() || verbosity() < 0 {
+        return;
+    }
+    let theme = theme();
+    crate::fli_println!("{} {}", theme.symbols.info.bold().color(theme.info), message);
+}
+
+/// Checks if the output is a terminal that has not been replaced
+pub(crate) fn is_output_terminal() -> bool {
+    OUTPUT.with(|output| output.borrow().is_none()) && io::stdout().is_terminal()
+}
+
+/// Checks if the error output, where progress is drawn, is a terminal that has not been replaced
+fn is_error_output_terminal() -> bool {
+    ERROR_OUTPUT.with(|output| output.borrow().is_none()) && io::stderr().is_terminal()
+}
+
+/// Formats a duration shortly, like `45s`, `3m05s` or `1h02m`
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    match seconds {
+        0..=59 => format!("{seconds}s"),
+        60..=3599 => format!("{}m{:02}s", seconds / 60, seconds % 60),
+        _ => format!("{}h{:02}m", seconds / 3600, seconds % 3600 / 60),
+    }
+}
+
+/// A progress bar drawn on the error output, like `[#####-----] 50/100 (50%) 12.5/s ETA 4s`
+///
+/// nothing is drawn when the error output is not a terminal or output is quiet (see `set_quiet`)
+///
+/// # Example
+/// ```
+/// use fli::display::ProgressBar;
+///
+/// let files = ["a.txt", "b.txt"];
+/// let mut progress = ProgressBar::new(files.len() as u64);
+/// for _file in files {
+///     // copy the file
+///     progress.inc(1);
+/// }
+/// progress.finish_with_message("copied 2 files");
+/// ```
+pub struct ProgressBar {
+    length: u64,
+    position: u64,
+    started: Instant,
+    last_draw: Option<Instant>,
+    hidden: bool,
+}
+
+impl ProgressBar {
+    /// The least time between two draws, so updating often does not flood the terminal
+    const DRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// Creates a progress bar going up to `length`
+    pub fn new(length: u64) -> Self {
+        Self {
+            length,
+            position: 0,
+            started: Instant::now(),
+            last_draw: None,
+            hidden: is_quiet() || !is_error_output_terminal(),
+        }
+    }
+
+    /// Changes the length, like when the total size is only known later
+    pub fn set_length(&mut self, length: u64) {
+        self.length = length;
+        self.draw(false);
+    }
+
+    /// Moves the progress to `position`
+    pub fn set_position(&mut self, position: u64) {
+        self.position = position.min(self.length);
+        self.draw(false);
+    }
+
+    /// Moves the progress forward by `delta`
+    pub fn inc(&mut self, delta: u64) {
+        self.set_position(self.position.saturating_add(delta));
+    }
+
+    /// Gets the current position
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Fills the bar and ends its line
+    pub fn finish(&mut self) {
+        self.position = self.length;
+        self.draw(true);
+        if !self.hidden {
+            eprint(format_args!("\n"));
+        }
+    }
+
+    /// Replaces the bar with a message
+    pub fn finish_with_message(&mut self, message: &str) {
+        self.position = self.length;
+        if !self.hidden {
+            eprint(format_args!("\r\x1b[2K{message}\n"));
+        }
+    }
+
+    /// Renders the bar as it would be drawn
+    ///
+    /// # Returns
+    /// * `String` - The bar, its position, the rate and the time left
+    pub fn render(&self) -> String {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let ratio = match self.length {
+            0 => 1.0,
+            length => self.position as f64 / length as f64,
+        };
+        let mut stats = format!("{}/{} ({}%)", self.position, self.length, (ratio * 100.0) as u64);
+        if elapsed > 0.0 && self.position > 0 {
+            let rate = self.position as f64 / elapsed;
+            stats.push_str(&format!(" {rate:.1}/s"));
+            if self.position < self.length {
+                let left = (self.length - self.position) as f64 / rate;
+                stats.push_str(&format!(" ETA {}", format_duration(Duration::from_secs_f64(left))));
+            }
+        }
+        let width = terminal_width().saturating_sub(stats.chars().count() + 3).clamp(10, 40);
+        let filled = ((ratio * width as f64) as usize).min(width);
+        format!("[{}{}] {stats}", "#".repeat(filled), "-".repeat(width - filled))
+    }
+
+    fn draw(&mut self, force: bool) {
+        if self.hidden {
+            return;
+        }
+        let now = Instant::now();
+        let due = self
+            .last_draw
+            .is_none_or(|last| now.duration_since(last) >= Self::DRAW_INTERVAL);
+        if force || due {
+            self.last_draw = Some(now);
+            eprint(format_args!("\r\x1b[2K{}", self.render()));
+        }
+    }
+}
+
+/// A spinner drawn on stderr for operations of unknown length, like `⠹ scanning files`
+///
+/// it animates on its own thread until finished or dropped,
+/// nothing is drawn when the error output is not a terminal or output is quiet (see `set_quiet`)
+///
+/// # Example
+/// ```
+/// use fli::display::Spinner;
+///
+/// let spinner = Spinner::start("scanning files");
+/// // scan
+/// spinner.message("scanning folders");
+/// // scan more
+/// spinner.finish_with_message("found 3 matches");
+/// ```
+pub struct Spinner {
+    /// The drawing thread, none when hidden
+    running: Option<SpinnerThread>,
+}
+
+/// The thread drawing a `Spinner` and what it shares with it
+struct SpinnerThread {
+    message: Arc<Mutex<String>>,
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+impl Spinner {
+    /// The time between two frames
+    const FRAME_INTERVAL: Duration = Duration::from_millis(80);
+
+    /// Starts spinning next to a message
+    pub fn start(message: &str) -> Self {
+        if is_quiet() || !is_error_output_terminal() {
+            return Self { running: None };
+        }
+        let message = Arc::new(Mutex::new(message.to_string()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let frames = theme().symbols.spinner;
+        let handle = {
+            let (message, stop) = (Arc::clone(&message), Arc::clone(&stop));
+            thread::spawn(move || {
+                for frame in frames.iter().cycle() {
+                    if stop.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let message = message.lock().map(|m| m.clone()).unwrap_or_default();
+                    let _ = write!(io::stderr(), "\r\x1b[2K{frame} {message}");
+                    thread::sleep(Self::FRAME_INTERVAL);
+                }
+            })
+        };
+        Self {
+            running: Some(SpinnerThread {
+                message,
+                stop,
+                handle,
+            }),
+        }
+    }
+
+    /// Checks if the spinner is not drawn
+    pub fn is_hidden(&self) -> bool {
+        self.running.is_none()
+    }
+
+    /// Changes the message next to the spinner
+    pub fn message(&self, message: &str) {
+        if let Some(running) = &self.running {
+            if let Ok(mut current) = running.message.lock() {
+                *current = message.to_string();
+            }
+        }
+    }
+
+    /// Stops and clears the spinner
+    pub fn finish(mut self) {
+        self.stop();
+    }
+
+    /// Stops the spinner and replaces it with a message
+    pub fn finish_with_message(mut self, message: &str) {
+        if self.stop() {
+            eprint(format_args!("{message}\n"));
+        }
+    }
+
+    /// Stops the drawing thread and clears the line, returning whether it was drawn
+    fn stop(&mut self) -> bool {
+        let Some(running) = self.running.take() else {
+            return false;
+        };
+        running.stop.store(true, Ordering::SeqCst);
+        let _ = running.handle.join();
+        eprint(format_args!("\r\x1b[2K"));
+        true
+    }
+}
+
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Writes to the output, use the `fli_println!` macro instead of calling this directly
+pub fn print(args: fmt::Arguments) {
+    OUTPUT.with(|output| match output.borrow_mut().as_mut() {
+        Some(writer) => {
+            let _ = writer.write_fmt(args);
+        }
+        None => {
+            let _ = io::stdout().write_fmt(args);
+        }
+    });
+}
+
+/// Writes to the error output, use the `fli_eprintln!` macro instead of calling this directly
+pub fn eprint(args: fmt::Arguments) {
+    ERROR_OUTPUT.with(|output| match output.borrow_mut().as_mut() {
+        Some(writer) => {
+            let _ = writer.write_fmt(args);
+        }
+        None => {
+            let _ = io::stderr().write_fmt(args);
+        }
+    });
+}
+
+/// Prints a line to the fli output (stdout unless replaced with `display::set_output`)
+#[macro_export]
+macro_rules! fli_println {
+    () => {
+        $crate::display::print(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::display::print(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+/// Prints a line to the fli error output (stderr unless replaced with `display::set_error_output`)
+#[macro_export]
+macro_rules! fli_eprintln {
+    () => {
+        $crate::display::eprint(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::display::eprint(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+/// Prints a debug message with `display::debug_print`, shown with `-vv` or `--debug`
+#[macro_export]
+macro_rules! fli_debug {
+    ($($arg:tt)*) => {
+        $crate::display::debug_print(format_args!($($arg)*))
+    };
+}
+
+/// A cloneable in memory writer used by `capture`
+#[derive(Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.0.borrow()).into_owned()
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Puts back the writers replaced by `capture`, even if the captured code panics
+struct RestoreOutput {
+    output: Option<Box<dyn Write>>,
+    error_output: Option<Box<dyn Write>>,
+}
+
+impl Drop for RestoreOutput {
+    fn drop(&mut self) {
+        OUTPUT.with(|output| *output.borrow_mut() = self.output.take());
+        ERROR_OUTPUT.with(|output| *output.borrow_mut() = self.error_output.take());
+    }
+}
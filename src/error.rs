@@ -0,0 +1,335 @@
+use std::fmt;
+
+use crate::messages::{fill, messages};
+
+/// The errors that can happen while parsing and running the app with `Fli::try_run`
+/// they compare equal field by field, so tests can `assert_eq!` the error they expect
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FliError {
+    /// An option that requires a value (`<>` or `<...>`) was passed without one
+    MissingValue {
+        /// The long name of the option
+        option: String,
+        /// The position of the option in the command line, the runner being 0
+        index: usize,
+    },
+    /// A value passed to an option is not one the option accepts
+    InvalidValue {
+        /// The long name of the option
+        option: String,
+        /// Why the value is not valid
+        message: String,
+        /// The position of the value in the command line, the runner being 0
+        index: usize,
+    },
+    /// An option that is not registered on the command was passed
+    UnknownOption {
+        /// The option as passed
+        option: String,
+        /// The registered options with a similar name
+        suggestions: Vec<String>,
+        /// The position of the option in the command line, the runner being 0
+        index: usize,
+    },
+    /// A command that requires one of its commands was run without one
+    MissingSubcommand {
+        /// The name of the command
+        command: String,
+    },
+    /// An abbreviated long option matches more than one option
+    AmbiguousOption {
+        /// The option as passed
+        option: String,
+        /// The long names of the options it could stand for
+        candidates: Vec<String>,
+        /// The position of the option in the command line, the runner being 0
+        index: usize,
+    },
+    /// Fewer positional args were passed than the command needs, see `Fli::min_positional_args`
+    MissingArguments {
+        /// The name of the command
+        command: String,
+        /// The fewest positional args the command needs
+        expected: usize,
+        /// The number of positional args passed
+        got: usize,
+        /// The usage line of the command, like `cp [options|commands] <arg> <arg> [arg]...`
+        usage: String,
+    },
+    /// More positional args were passed than the command takes, see `Fli::max_positional_args`
+    TooManyArguments {
+        /// The first positional arg past the maximum
+        argument: String,
+        /// The most positional args the command takes
+        expected: usize,
+        /// The number of positional args passed
+        got: usize,
+        /// The position of the first arg past the maximum in the command line, the runner being 0
+        index: usize,
+    },
+    /// A validator added with `Fli::validate` rejected the args
+    ValidationFailed {
+        /// The name of the command
+        command: String,
+        /// Why the args are not valid
+        message: String,
+    },
+    /// An option set with `Fli::exclusive` was passed along with another option or a positional arg
+    ExclusiveOption {
+        /// The long name of the exclusive option
+        option: String,
+        /// The first other arg, as passed
+        other: String,
+        /// The position of the other arg in the command line, the runner being 0
+        index: usize,
+    },
+    /// More than one error in the args, with `Fli::collect_errors`
+    Multiple {
+        /// The errors in the order they were found
+        errors: Vec<FliError>,
+    },
+    /// An error in a command of the app, as returned by `Fli::try_run`, its message names the command
+    /// like `error in 'app remote add': Unknown option -x`
+    InCommand {
+        /// The names of the commands leading to the one the error happened in, the app first, see `Fli::command_path`
+        command_path: Vec<String>,
+        /// The error
+        error: Box<FliError>,
+    },
+}
+
+/// The kinds of `FliError`, used to pick the exit code of each with `Fli::set_exit_code`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FliErrorKind {
+    /// `FliError::MissingValue`
+    MissingValue,
+    /// `FliError::InvalidValue`
+    InvalidValue,
+    /// `FliError::UnknownOption`
+    UnknownOption,
+    /// `FliError::MissingSubcommand`
+    MissingSubcommand,
+    /// `FliError::AmbiguousOption`
+    AmbiguousOption,
+    /// `FliError::MissingArguments`
+    MissingArguments,
+    /// `FliError::TooManyArguments`
+    TooManyArguments,
+    /// `FliError::ValidationFailed`
+    ValidationFailed,
+    /// `FliError::ExclusiveOption`
+    ExclusiveOption,
+    /// `FliError::Multiple`
+    Multiple,
+}
+
+impl FliErrorKind {
+    /// Every kind of error
+    pub const ALL: [FliErrorKind; 10] = [
+        FliErrorKind::MissingValue,
+        FliErrorKind::InvalidValue,
+        FliErrorKind::UnknownOption,
+        FliErrorKind::MissingSubcommand,
+        FliErrorKind::AmbiguousOption,
+        FliErrorKind::MissingArguments,
+        FliErrorKind::TooManyArguments,
+        FliErrorKind::ValidationFailed,
+        FliErrorKind::ExclusiveOption,
+        FliErrorKind::Multiple,
+    ];
+
+    /// The exit code `Fli::run` uses for this kind when none was set,
+    /// 2 like most tools since every kind is an error in how the app was called
+    pub fn default_exit_code(&self) -> i32 {
+        2
+    }
+
+    /// The exit code of this kind in the BSD sysexits table, used by `Fli::use_sysexits`
+    ///
+    /// | Kind | Code |
+    /// |------|------|
+    /// | `InvalidValue`, `ValidationFailed` | 65 (`EX_DATAERR`), the values passed are wrong |
+    /// | the others | 64 (`EX_USAGE`), the app was called the wrong way |
+    pub fn sysexits_code(&self) -> i32 {
+        match self {
+            FliErrorKind::InvalidValue | FliErrorKind::ValidationFailed => 65,
+            _ => 64,
+        }
+    }
+}
+
+impl FliError {
+    /// Gets the kind of the error
+    pub fn kind(&self) -> FliErrorKind {
+        match self {
+            FliError::MissingValue { .. } => FliErrorKind::MissingValue,
+            FliError::InvalidValue { .. } => FliErrorKind::InvalidValue,
+            FliError::UnknownOption { .. } => FliErrorKind::UnknownOption,
+            FliError::MissingSubcommand { .. } => FliErrorKind::MissingSubcommand,
+            FliError::AmbiguousOption { .. } => FliErrorKind::AmbiguousOption,
+            FliError::MissingArguments { .. } => FliErrorKind::MissingArguments,
+            FliError::TooManyArguments { .. } => FliErrorKind::TooManyArguments,
+            FliError::ValidationFailed { .. } => FliErrorKind::ValidationFailed,
+            FliError::ExclusiveOption { .. } => FliErrorKind::ExclusiveOption,
+            FliError::Multiple { .. } => FliErrorKind::Multiple,
+            FliError::InCommand { error, .. } => error.kind(),
+        }
+    }
+
+    /// Gets the error without the command path of `FliError::InCommand`, the error itself for the others
+    ///
+    /// # Example
+    /// ```
+    /// use fli::FliError;
+    ///
+    /// let error = FliError::InCommand {
+    ///     command_path: vec!["app".to_string(), "remote".to_string()],
+    ///     error: Box::new(FliError::MissingSubcommand { command: "remote".to_string() }),
+    /// };
+    /// assert!(matches!(error.inner(), FliError::MissingSubcommand { .. }));
+    /// ```
+    pub fn inner(&self) -> &FliError {
+        match self {
+            FliError::InCommand { error, .. } => error.inner(),
+            error => error,
+        }
+    }
+
+    /// Gets the position in the command line of the arg the error is about, the runner being 0
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            FliError::MissingValue { index, .. }
+            | FliError::InvalidValue { index, .. }
+            | FliError::UnknownOption { index, .. }
+            | FliError::AmbiguousOption { index, .. }
+            | FliError::TooManyArguments { index, .. }
+            | FliError::ExclusiveOption { index, .. } => Some(*index),
+            FliError::InCommand { error, .. } => error.index(),
+            FliError::MissingSubcommand { .. }
+            | FliError::MissingArguments { .. }
+            | FliError::ValidationFailed { .. }
+            | FliError::Multiple { .. } => None,
+        }
+    }
+
+    /// Gets the message of the error naming the command it happened in, like `error in 'app remote add': Unknown option -x`
+    /// just the message when the path is only the app, an `InCommand` error uses its own path
+    /// # Arguments
+    /// * `command_path` - The names of the commands leading to the one the error happened in, the app first, see `Fli::command_path`
+    ///
+    /// # Example
+    /// ```
+    /// use fli::FliError;
+    ///
+    /// let error = FliError::MissingSubcommand { command: "add".to_string() };
+    /// assert_eq!(error.message_in(&["app", "remote", "add"]), "error in 'app remote add': add requires a command");
+    /// assert_eq!(error.message_in(&["app"]), "add requires a command");
+    /// ```
+    ///
+    /// # Returns
+    /// * `String` - The message
+    pub fn message_in(&self, command_path: &[&str]) -> String {
+        if let FliError::InCommand { command_path, error } = self {
+            let command_path: Vec<&str> = command_path.iter().map(String::as_str).collect();
+            return error.message_in(&command_path);
+        }
+        if command_path.len() < 2 {
+            return self.to_string();
+        }
+        fill(
+            &messages().error_in_command,
+            &[("command", &command_path.join(" ")), ("message", &self.to_string())],
+        )
+    }
+
+    /// Renders the command line with carets under the arg the error is about
+    /// # Arguments
+    /// * `args` - The command line, the runner first
+    ///
+    /// # Example
+    /// ```
+    /// use fli::FliError;
+    ///
+    /// let error = FliError::UnknownOption {
+    ///     option: "--sot".to_string(),
+    ///     suggestions: vec!["--sort".to_string()],
+    ///     index: 2,
+    /// };
+    /// let args = ["myapp", "ls", "--sot", "name"].map(String::from);
+    /// assert_eq!(error.render_span(&args).unwrap(), "myapp ls --sot name\n         ^^^^^");
+    /// ```
+    ///
+    /// # Returns
+    /// * `Option<String>` - The two lines, none if the error is not about a single arg
+    pub fn render_span(&self, args: &[String]) -> Option<String> {
+        let index = self.index()?;
+        let arg = args.get(index)?;
+        let start: usize = args[..index].iter().map(|arg| arg.chars().count() + 1).sum();
+        Some(format!(
+            "{}\n{}{}",
+            args.join(" "),
+            " ".repeat(start),
+            "^".repeat(arg.chars().count().max(1))
+        ))
+    }
+}
+
+impl fmt::Display for FliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages = messages();
+        let message = match self {
+            FliError::MissingValue { option, .. } => fill(&messages.missing_value, &[("option", option)]),
+            FliError::InvalidValue { option, message, .. } => fill(
+                &messages.invalid_value,
+                &[("option", option), ("message", message)],
+            ),
+            FliError::UnknownOption { option, .. } => fill(&messages.unknown_option, &[("option", option)]),
+            FliError::MissingSubcommand { command } => {
+                fill(&messages.missing_subcommand, &[("command", command)])
+            }
+            FliError::AmbiguousOption { option, candidates, .. } => fill(
+                &messages.ambiguous_option,
+                &[("option", option), ("candidates", &candidates.join(", "))],
+            ),
+            FliError::MissingArguments { command, expected, got, usage } => fill(
+                &messages.missing_arguments,
+                &[
+                    ("command", command),
+                    ("expected", &expected.to_string()),
+                    ("got", &got.to_string()),
+                    ("usage", usage),
+                ],
+            ),
+            FliError::TooManyArguments { argument, expected, got, .. } => fill(
+                &messages.too_many_arguments,
+                &[
+                    ("argument", argument),
+                    ("expected", &expected.to_string()),
+                    ("got", &got.to_string()),
+                ],
+            ),
+            FliError::ValidationFailed { message, .. } => {
+                fill(&messages.validation_failed, &[("message", message)])
+            }
+            FliError::ExclusiveOption { option, other, .. } => fill(
+                &messages.exclusive_option,
+                &[("option", option), ("other", other)],
+            ),
+            FliError::Multiple { errors } => {
+                let messages: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+                messages.join("\n")
+            }
+            FliError::InCommand { .. } => self.message_in(&[]),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl std::error::Error for FliError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FliError::InCommand { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
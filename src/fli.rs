@@ -1,7 +1,296 @@
 use colored::Colorize;
-use std::{collections::HashMap, env, process};
+use std::{cell::{Cell, OnceCell}, collections::HashMap, env, path::PathBuf, rc::Rc, time::Duration};
 
-use crate::{fli_default_callback, levenshtein_distance};
+use crate::{
+    config,
+    display::{self, ColorPolicy, Table, Theme},
+    error::{FliError, FliErrorKind},
+    messages::{self, fill, Messages},
+    catch_exit, exit, fli_default_callback, fli_eprintln, fli_println, is_negative_number,
+    levenshtein_distance,
+    parse_duration, parse_size, paths, prompt, quote_shell_word, read_response_file,
+    recall_history_line, split_shell_words,
+    value::FliValue,
+};
+
+/// A function checking the args of a command, returning why they are not valid, see `Fli::validate`
+pub type Validator = fn(app: &Fli) -> Result<(), String>;
+
+/// A function checking the value of a typed option, returning why it is not valid
+type ValueCheck = fn(value: &str) -> Result<(), String>;
+
+/// The placeholders a help template can use, each replaced by a section of the help screen
+pub const HELP_TEMPLATE_PLACEHOLDERS: [&str; 7] = [
+    "{name}",
+    "{version}",
+    "{description}",
+    "{usage}",
+    "{arguments}",
+    "{options}",
+    "{subcommands}",
+];
+
+/// The help template used when none is set with `Fli::set_help_template`
+pub const DEFAULT_HELP_TEMPLATE: &str =
+    "{name}\n{version}\n{description}\n{usage}\n{arguments}\n{options}\n{subcommands}";
+
+/// The priority of the help option, its callback runs before the ones of other passed options, see `Fli::set_option_priority`
+pub const HELP_PRIORITY: i32 = 20;
+
+/// The priority of the version option, below the help option so `--version --help` prints the help screen
+pub const VERSION_PRIORITY: i32 = 10;
+
+/// The order options and commands are listed in on the help screen, set with `Fli::set_help_order`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HelpOrder {
+    /// In the order they were added to the app
+    #[default]
+    Declaration,
+    /// By long option name or command name
+    Alphabetical,
+}
+
+/// Renders the help screen of an app or command, set with `Fli::set_help_renderer` to replace the whole layout
+///
+/// the app and its options, positional args and commands are read with `get_app_name`, `get_description`,
+/// `get_usage`, `options`, `arguments` and `commands`
+///
+/// # Example
+/// ```
+/// struct CompactHelp;
+///
+/// impl HelpRenderer for CompactHelp {
+///     fn render(&self, app: &Fli) -> String {
+///         let mut help = format!("usage: {}\n", app.get_usage());
+///         for option in app.options() {
+///             help += &format!("  {:<12} {}\n", option.long, option.description);
+///         }
+///         help
+///     }
+/// }
+///
+/// app.set_help_renderer(Box::new(CompactHelp));
+/// ```
+pub trait HelpRenderer {
+    /// Renders the help screen, printed as it is (or through the pager when enabled)
+    /// # Arguments
+    /// * `app` - The app or command the help is for
+    ///
+    /// # Returns
+    /// * `String` - The help screen
+    fn render(&self, app: &Fli) -> String;
+}
+
+/// The help renderer used when none is set, laying out the help screen with the help template
+/// and the `before_help` and `after_help` texts, use it to add to the default help screen
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultHelpRenderer;
+
+impl HelpRenderer for DefaultHelpRenderer {
+    fn render(&self, app: &Fli) -> String {
+        let (_, help, _) = display::capture(|| app.print_help_sections());
+        help
+    }
+}
+
+/// Renders the errors `Fli::run` stops on, set with `Fli::set_error_renderer` to print them as single lines,
+/// JSON or anything else, the app still exits with the exit code of the error
+///
+/// # Example
+/// ```
+/// struct JsonErrors;
+///
+/// impl ErrorRenderer for JsonErrors {
+///     fn render(&self, _app: &Fli, error: &FliError) -> String {
+///         format!("{{\"kind\": \"{:?}\", \"message\": \"{error}\"}}\n", error.kind())
+///     }
+/// }
+///
+/// app.set_error_renderer(Box::new(JsonErrors));
+/// ```
+pub trait ErrorRenderer {
+    /// Renders an error, printed as it is (or through the pager when enabled)
+    /// # Arguments
+    /// * `app` - The app or command the error happened in
+    /// * `error` - The error
+    ///
+    /// # Returns
+    /// * `String` - The rendered error
+    fn render(&self, app: &Fli, error: &FliError) -> String;
+}
+
+/// The error renderer used when none is set, showing the error in a banner with carets under the arg it is about,
+/// the `Did you mean:` suggestions, as a corrected command line when there is only one, and the help screen.
+/// Errors in commands name the command path
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultErrorRenderer;
+
+impl ErrorRenderer for DefaultErrorRenderer {
+    fn render(&self, app: &Fli, error: &FliError) -> String {
+        let errors = match error.inner() {
+            FliError::Multiple { errors } => errors.as_slice(),
+            error => std::slice::from_ref(error),
+        };
+        let (_, rendered, _) = display::capture(|| {
+            for error in errors {
+                app.print_error_banner(&error.message_in(&app.command_path()));
+                let theme = display::theme();
+                if let Some(span) = error.render_span(app.get_command_line()) {
+                    for line in span.lines() {
+                        fli_println!("{0: <5} {1}", "", line.color(theme.error));
+                    }
+                }
+                if let FliError::UnknownOption { suggestions, index, .. } = error {
+                    app.print_suggestions(suggestions, Some(*index));
+                }
+            }
+        });
+        rendered + &app.help_screen()
+    }
+}
+
+/// What the app does once a callback returns, a callback asks to stop with `Fli::request_exit`
+/// like the help and version options do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Action {
+    /// Run the next callbacks
+    #[default]
+    Continue,
+    /// Skip the next callbacks, `run` exits with the code and `try_run` returns
+    Exit(i32),
+}
+
+/// How an option behaves when it is passed, set with `Fli::set_arg_action`
+///
+/// options without one run their callback and `get_values` reads their first occurrence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgAction {
+    /// Takes a value, passing the option again replaces it so `get_values` reads the last occurrence
+    Set,
+    /// A flag `get_flag` reads as true when passed, false otherwise
+    SetTrue,
+    /// A flag `get_flag` reads as false when passed, true otherwise, like `--no-color`
+    SetFalse,
+    /// A flag that can be repeated, `get_count` reads how many times it was passed like `-vvv`
+    Count,
+    /// Takes a value, `get_values` reads the values of every occurrence like `-I src -I lib`
+    Append,
+    /// Prints the help screen and stops, like `--help`
+    Help,
+    /// Prints `name version` and stops, like `--version`
+    Version,
+}
+
+/// What the value of an option is, set with `Fli::value_hint` for the completion specs and `Fli::ask_value`
+#[derive(Debug, Clone)]
+pub enum ValueHint {
+    /// A path to a file
+    FilePath,
+    /// A path to a directory
+    DirPath,
+    /// A host name or address
+    Hostname,
+    /// One of a list of values
+    Choices(Vec<String>),
+    /// One of the values a function gives, read when the specs are written or the value is asked
+    Custom(fn() -> Vec<String>),
+}
+
+impl ValueHint {
+    /// Gets the values to pick from, empty for paths and host names
+    pub fn values(&self) -> Vec<String> {
+        match self {
+            ValueHint::Choices(values) => values.clone(),
+            ValueHint::Custom(values) => values(),
+            _ => vec![],
+        }
+    }
+
+    /// Gets the name of the hint in `OptionInfo::value_hint`
+    fn name(&self) -> &'static str {
+        match self {
+            ValueHint::FilePath => "file",
+            ValueHint::DirPath => "dir",
+            ValueHint::Hostname => "hostname",
+            ValueHint::Choices(_) | ValueHint::Custom(_) => "choice",
+        }
+    }
+
+    /// Gets the hint from its name in `OptionInfo::value_hint`, none for an empty or unknown name
+    fn from_name(name: &str, values: &[String]) -> Option<ValueHint> {
+        match name {
+            "file" => Some(ValueHint::FilePath),
+            "dir" => Some(ValueHint::DirPath),
+            "hostname" => Some(ValueHint::Hostname),
+            "choice" => Some(ValueHint::Choices(values.to_vec())),
+            _ => None,
+        }
+    }
+}
+
+/// What to do with options a command does not have, set with `Fli::set_unknown_option_policy`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnknownOptionPolicy {
+    /// Stop with an `UnknownOption` error
+    #[default]
+    Error,
+    /// Print a warning and ignore them
+    Warn,
+    /// Collect them in `Fli::unknown_args`, for apps forwarding them to another program
+    Collect,
+}
+
+/// An option of a command, as listed by `Fli::options`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OptionInfo {
+    /// The long name, like `--name`
+    pub long: String,
+    /// The short name, like `-n`
+    pub short: Option<String>,
+    /// The param type template: `""` for a flag, `[]`, `<>`, `[...]` or `<...>`
+    pub param: String,
+    /// The description in the help screen
+    pub description: String,
+    /// What the value is: `""` for none, `file`, `dir`, `hostname` or `choice`, see `ValueHint`
+    pub value_hint: String,
+    /// The values to pick from when the hint is `choice`
+    pub choices: Vec<String>,
+}
+
+/// A positional arg of a command described with `Fli::argument`, as listed by `Fli::arguments`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ArgumentInfo {
+    /// The name without brackets, like `source`
+    pub name: String,
+    /// The param type template: `[]`, `<>`, `[...]` or `<...>`
+    pub param: String,
+    /// The description in the help screen
+    pub description: String,
+}
+
+/// The definition of an app or command and the commands under it, from `Fli::to_spec`
+/// it can be serialized and deserialized with the `serde` feature, to compare the commands and options of two releases for example
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct CliSpec {
+    /// The name of the app or command
+    pub name: String,
+    /// The description of the app or command
+    pub description: String,
+    /// The version of the app
+    pub version: String,
+    /// The options in the order of the help screen
+    pub options: Vec<OptionInfo>,
+    /// The positional args described with `Fli::argument`
+    pub arguments: Vec<ArgumentInfo>,
+    /// The commands in the order of the help screen
+    pub commands: Vec<CliSpec>,
+}
 
 /// This is the main struct that holds all the data
 ///
@@ -11,11 +300,12 @@ use crate::{fli_default_callback, levenshtein_distance};
 /// app.option("-n --name", "The name of the user", |x| {
 ///    let name = x.get_values("-n".to_string());
 ///    if !name.is_err() {
-///     println!("Hello {}", name.unwrap().get(0));
+///     fli_println!("Hello {}", name.unwrap().get(0));
 ///    }
 /// });
 /// ```
 ///
+#[derive(Clone)]
 pub struct Fli {
     /// The name of the app
     name: String,
@@ -43,6 +333,143 @@ pub struct Fli {
     allow_duplicate_callback: bool,
     /// A boolean to allow initial no param values
     allow_inital_no_param_values: bool,
+    /// A boolean to collect everything after `--` or the first unknown value as trailing args
+    allow_trailing_args: bool,
+    /// A boolean to read the args starting with `-` that are not options as values
+    allow_hyphen_values: bool,
+    /// A boolean to leave the args from the last positional arg described with `argument` untouched
+    last_positional_captures_rest: bool,
+    /// A boolean to accept unique prefixes of long options, like `--verb` for `--verbose`
+    allow_abbreviations: bool,
+    /// A boolean to accept `_` for `-` in long options and the other way around, like `--max_depth` for `--max-depth`
+    allow_any_separator: bool,
+    /// What to do with options the command does not have
+    unknown_options: UnknownOptionPolicy,
+    /// A boolean to replace `@file` args with the args in the file
+    response_files: bool,
+    /// A boolean to read Windows style options like `/r` and `/output:file.txt`
+    slash_options: bool,
+    /// Whether the built-in `--color` option is registered, an option of the app named `--color` replaces it
+    builtin_color: bool,
+    /// A boolean to report every error in the args at once instead of the first one
+    collect_errors: bool,
+    /// The errors collected by the parents of the command before it was dispatched, reported with its own
+    parent_errors: Vec<FliError>,
+    /// A boolean to show long help screens through `$PAGER`
+    help_pager: bool,
+    /// The layout of the help screen, `DEFAULT_HELP_TEMPLATE` when not set
+    help_template: Option<String>,
+    /// What renders the help screen, the parent's (or `DefaultHelpRenderer`) when not set
+    help_renderer: Option<Rc<dyn HelpRenderer>>,
+    /// What renders the errors `run` stops on, the parent's (or `DefaultErrorRenderer`) when not set
+    error_renderer: Option<Rc<dyn ErrorRenderer>>,
+    /// What to do once the running callback returns, set with `request_exit`
+    action: Cell<Action>,
+    /// The keys of `help_hash_table` in the order they were added
+    help_order: Vec<String>,
+    /// The order of options and commands in the help screen, the parent's (or declaration order) when not set
+    help_sort: Option<HelpOrder>,
+    /// The usage line of the help screen, `<name> [options|commands]` when not set
+    usage: Option<String>,
+    /// Free-form text printed above the help screen
+    before_help: Option<String>,
+    /// Free-form text printed below the help screen
+    after_help: Option<String>,
+    /// The description `--help` shows instead of the short one, set by `long_help`
+    long_help: Option<String>,
+    /// The descriptions `--help` shows for options by long name, set by `option_long_help`
+    option_long_helps: HashMap<String, String>,
+    /// Whether the help screen being rendered is the long one of `--help` rather than the short one of `-h`
+    show_long_help: bool,
+    /// The hash table for deprecations where the key is the long option or command name and the value is the replacement hint
+    deprecated_hash_table: HashMap<String, String>,
+    /// The command to run when no command is passed
+    default_command: Option<String>,
+    /// The callback to run with the raw args when a command that does not exist is passed
+    unmatched_callback: Option<fn(app: &Self, args: &[String])>,
+    /// A boolean to error when none of the commands is passed
+    subcommand_required: bool,
+    /// A boolean to read values after the first positional arg as positional args even when they name a command
+    prefer_positionals_after_args: bool,
+    /// The fewest positional args the command needs
+    min_positional_args: usize,
+    /// The most positional args the command takes, any number when not set
+    max_positional_args: Option<usize>,
+    /// The positional args listed in the help screen, their name template (`<file>`, `[dest]`, `<files>...`) and description
+    arguments: Vec<(String, String)>,
+    /// A boolean to print the help screen when no arg is passed
+    arg_required_else_help: bool,
+    /// The hooks to run before the callbacks of this command
+    before_hooks: Vec<fn(app: &Self)>,
+    /// The hooks to run after the callbacks of this command
+    after_hooks: Vec<fn(app: &Self)>,
+    /// The hooks to run before the callbacks of this command and every command under it
+    before_each_hooks: Vec<fn(app: &Self)>,
+    /// The hooks to run after the callbacks of this command and every command under it
+    after_each_hooks: Vec<fn(app: &Self)>,
+    /// The functions checking the args of this command before its callbacks run, set by `validate`
+    validators: Vec<Validator>,
+    /// The function `run` hands errors to instead of printing the help screen, it returns the exit code
+    error_handler: Option<fn(error: &FliError) -> i32>,
+    /// The hash table for exit codes where the key is the error kind and the value is the code `run` exits with
+    exit_codes: HashMap<FliErrorKind, i32>,
+    /// The whole command line when this is a command being run, empty on the app itself
+    command_line: Vec<String>,
+    /// The position of this command in the command line
+    arg_offset: usize,
+    /// The colors and symbols of the output, applied when the app runs
+    theme: Option<Theme>,
+    /// The text of the output, applied when the app runs
+    messages: Option<Messages>,
+    /// Whether the output only uses ASCII symbols, detected when not set
+    ascii_output: Option<bool>,
+    /// Whether `add_verbosity_flags` was called, commands created after it get the flags too
+    verbosity_flags: bool,
+    /// The verbosity from the options passed to the parents of this command
+    parent_verbosity: i8,
+    /// The config file read when `--config` is not passed, set by `enable_config_file`
+    config_file: Option<String>,
+    /// The names of the commands leading to this one, the section of the config file it reads
+    config_section: Vec<String>,
+    /// The config file once loaded, none when it is missing
+    config: OnceCell<Result<Option<config::Section>, String>>,
+    /// The `.env` file to load into the environment before parsing, empty for the closest `.env`
+    #[cfg(feature = "dotenv")]
+    dotenv_file: Option<String>,
+    /// The header this command is listed under in the help screen of its parent
+    category: Option<String>,
+    /// The command this one was called from, with the args passed before this one
+    parent: Option<Box<Fli>>,
+    /// The long names of the options commands get a copy of, set by `mark_inheritable`
+    inheritable_options: Vec<String>,
+    /// The long names of the options copied from the parent, replaced when the command adds its own
+    inherited_options: Vec<String>,
+    /// Whether options added later are inheritable too, set by `mark_all_inheritable`
+    all_inheritable: bool,
+    /// The header each option is listed under in the help screen by long name, set by `add_option_to_group`
+    option_groups: HashMap<String, String>,
+    /// The functions giving the value of an option when it is not passed by long name, set by `default_with`
+    dynamic_defaults: HashMap<String, fn(app: &Fli) -> String>,
+    /// The value of an option when it is not passed by long name, set by `default_value`
+    default_values: HashMap<String, String>,
+    /// The checks of the values of the typed options by long name, set by `add_int_option` and the like
+    value_checks: HashMap<String, ValueCheck>,
+    /// How each option behaves when passed by long name, set by `set_arg_action`
+    arg_actions: HashMap<String, ArgAction>,
+    /// What the value of each option is by long name, set by `value_hint`
+    value_hints: HashMap<String, ValueHint>,
+    /// The fewest and most values of options, set by `value_count`
+    value_counts: HashMap<String, (usize, Option<usize>)>,
+    /// The token ending the values of options taking many values, set by `value_terminator`
+    value_terminator: Option<String>,
+    /// The long names of the options that can not be passed with any other arg, set by `exclusive`
+    exclusive_options: Vec<String>,
+    /// The long option each name set by `set_option_name` stands for, like `human` for `--human-readable`
+    option_names: HashMap<String, String>,
+    /// The command each hidden alias stands for, set by `hidden_command_alias`
+    command_aliases: HashMap<String, String>,
+    /// The priority of the callback of each option by long name, 0 when not set, set by `set_option_priority`
+    option_priorities: HashMap<String, i32>,
 }
 
 impl Fli {
@@ -69,7 +496,7 @@ impl Fli {
         let version = env!("CARGO_PKG_VERSION");
         let mut app = Self::init(name, description);
         app.set_version(version);
-        return app;
+        app
     }
 
     /// Initializes the Fli struct with the name and description
@@ -97,10 +524,80 @@ impl Fli {
             default_callback: fli_default_callback,
             allow_duplicate_callback: false,
             allow_inital_no_param_values: false,
+            allow_trailing_args: false,
+            allow_hyphen_values: false,
+            last_positional_captures_rest: false,
+            allow_abbreviations: false,
+            allow_any_separator: false,
+            unknown_options: UnknownOptionPolicy::Error,
+            response_files: false,
+            slash_options: false,
+            builtin_color: false,
+            collect_errors: false,
+            parent_errors: Vec::new(),
+            help_pager: false,
+            help_template: None,
+            help_renderer: None,
+            error_renderer: None,
+            action: Cell::new(Action::Continue),
+            help_order: vec![],
+            help_sort: None,
+            usage: None,
+            before_help: None,
+            after_help: None,
+            long_help: None,
+            option_long_helps: HashMap::new(),
+            show_long_help: false,
+            deprecated_hash_table: HashMap::new(),
+            default_command: None,
+            unmatched_callback: None,
+            subcommand_required: false,
+            prefer_positionals_after_args: false,
+            min_positional_args: 0,
+            max_positional_args: None,
+            arguments: vec![],
+            arg_required_else_help: false,
+            before_hooks: vec![],
+            after_hooks: vec![],
+            before_each_hooks: vec![],
+            after_each_hooks: vec![],
+            validators: vec![],
+            error_handler: None,
+            exit_codes: HashMap::new(),
+            command_line: vec![],
+            arg_offset: 0,
+            theme: None,
+            messages: None,
+            ascii_output: None,
+            verbosity_flags: false,
+            parent_verbosity: 0,
+            config_file: None,
+            config_section: vec![],
+            config: OnceCell::new(),
+            #[cfg(feature = "dotenv")]
+            dotenv_file: None,
+            category: None,
+            parent: None,
+            inheritable_options: vec![],
+            inherited_options: vec![],
+            all_inheritable: false,
+            option_groups: HashMap::new(),
+            option_priorities: HashMap::new(),
+            dynamic_defaults: HashMap::new(),
+            default_values: HashMap::new(),
+            value_checks: HashMap::new(),
+            arg_actions: HashMap::new(),
+            value_hints: HashMap::new(),
+            value_counts: HashMap::new(),
+            value_terminator: None,
+            exclusive_options: vec![],
+            option_names: HashMap::new(),
+            command_aliases: HashMap::new(),
         };
         app.add_help_option();
         app.add_version_option();
-        return app;
+        app.add_color_option();
+        app
     }
 
     /// Creates a new command
@@ -129,7 +626,7 @@ impl Fli {
     ///   let time_saying: String = match time {
     ///      _ => String::from("Hello"),
     ///   };
-    ///   println!("{time_saying} {name}")
+    ///   fli_println!("{time_saying} {name}")
     /// }
     /// ```
     /// 
@@ -138,14 +635,14 @@ impl Fli {
     pub fn command(&mut self, name: &str, description: &str) -> &mut Fli {
         let mut args = self.args.clone();
         // check for zero index if available remove it
-        if args.len() > 0 {
+        if !args.is_empty() {
             args.remove(0);
         }
         let mut new_fli = Self {
             name: name.to_string(),
             description: description.to_string(),
             version: self.version.to_string(),
-            args: args,
+            args,
             args_hash_table: HashMap::new(),
             short_hash_table: HashMap::new(),
             cammands_hash_tables: HashMap::new(),
@@ -153,21 +650,129 @@ impl Fli {
             default_callback: fli_default_callback,
             allow_duplicate_callback: self.allow_duplicate_callback,
             allow_inital_no_param_values: self.allow_inital_no_param_values,
+            allow_trailing_args: self.allow_trailing_args,
+            allow_hyphen_values: self.allow_hyphen_values,
+            last_positional_captures_rest: false,
+            allow_abbreviations: self.allow_abbreviations,
+            allow_any_separator: self.allow_any_separator,
+            unknown_options: self.unknown_options,
+            response_files: self.response_files,
+            slash_options: self.slash_options,
+            builtin_color: false,
+            collect_errors: self.collect_errors,
+            parent_errors: Vec::new(),
+            help_pager: self.help_pager,
+            help_template: None,
+            help_renderer: None,
+            error_renderer: None,
+            action: Cell::new(Action::Continue),
+            help_order: vec![],
+            help_sort: None,
+            usage: None,
+            before_help: None,
+            after_help: None,
+            long_help: None,
+            option_long_helps: HashMap::new(),
+            show_long_help: false,
+            deprecated_hash_table: HashMap::new(),
+            default_command: None,
+            unmatched_callback: None,
+            subcommand_required: false,
+            prefer_positionals_after_args: false,
+            min_positional_args: 0,
+            max_positional_args: None,
+            arguments: vec![],
+            arg_required_else_help: false,
+            before_hooks: vec![],
+            after_hooks: vec![],
+            before_each_hooks: vec![],
+            after_each_hooks: vec![],
+            validators: vec![],
+            error_handler: None,
+            exit_codes: HashMap::new(),
+            command_line: vec![],
+            arg_offset: 0,
+            theme: None,
+            messages: None,
+            ascii_output: None,
+            verbosity_flags: false,
+            parent_verbosity: 0,
+            config_file: None,
+            config_section: vec![],
+            config: OnceCell::new(),
+            #[cfg(feature = "dotenv")]
+            dotenv_file: None,
+            category: None,
+            parent: None,
+            inheritable_options: vec![],
+            inherited_options: vec![],
+            all_inheritable: false,
+            option_groups: HashMap::new(),
+            option_priorities: HashMap::new(),
+            dynamic_defaults: HashMap::new(),
+            default_values: HashMap::new(),
+            value_checks: HashMap::new(),
+            arg_actions: HashMap::new(),
+            value_hints: HashMap::new(),
+            value_counts: HashMap::new(),
+            value_terminator: None,
+            exclusive_options: vec![],
+            option_names: HashMap::new(),
+            command_aliases: HashMap::new(),
         };
         new_fli.add_help_option();
+        new_fli.add_color_option();
+        if self.verbosity_flags {
+            new_fli.add_verbosity_flags();
+        }
+        if let Some(path) = self.config_file.clone() {
+            new_fli.enable_config_file(&path);
+        }
+        for long in &self.inheritable_options {
+            new_fli.inherit_option(self, long);
+        }
         self.cammands_hash_tables.insert(name.to_string(), new_fli);
-        self.help_hash_table
-            .insert(name.to_string(), description.to_string());
-        return self
+        self.insert_help(name.to_string(), description.to_string());
+        self
             .cammands_hash_tables
-            .get_mut(&name.to_string())
-            .unwrap();
+            .get_mut(name)
+            .unwrap()
+    }
+
+    /// Creates a command under other commands from its path, so deep trees do not need a variable for each level
+    /// the commands on the way are created when missing, without a description
+    /// # Arguments
+    /// * `path` - The names of the commands leading to it then its name, like `remote add`
+    /// * `description` - The description of the command
+    ///
+    /// # Example
+    /// ```
+    /// let mut app : Fli = Fli::init("name", "a sample app");
+    /// app.command("remote", "Manage remotes");
+    /// app.command_at("remote add", "Add a remote").default(add_remote);
+    /// app.command_at("remote branch delete", "Delete a remote branch");
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The new command, the app itself for an empty path
+    pub fn command_at(&mut self, path: &str, description: &str) -> &mut Fli {
+        let mut names: Vec<&str> = path.split_whitespace().collect();
+        let Some(name) = names.pop() else {
+            return self;
+        };
+        let mut parent = self;
+        for name in names {
+            if !parent.cammands_hash_tables.contains_key(name) {
+                parent.command(name, "");
+            }
+            parent = parent.cammands_hash_tables.get_mut(name).unwrap();
+        }
+        parent.command(name, description)
     }
 
     /// To set the version of the app
     /// # Arguments
     /// * `version` - The version of the app
-    
     pub fn set_version(&mut self, version: &str) -> &mut Self {
         self.version = version.to_string();
         self
@@ -177,6 +782,13 @@ impl Fli {
         self.version.to_owned()
     }
 
+    /// Replaces the arguments the app parses, the first one being the app runner
+    pub(crate) fn set_args(&mut self, args: Vec<String>) {
+        self.args = args;
+        // `--config` may point somewhere else now
+        self.config = OnceCell::new();
+    }
+
     /// Allows duplicate callback
     /// # Arguments
     /// * `data` - A boolean to allow duplicate callback
@@ -212,364 +824,4101 @@ impl Fli {
         self
     }
 
+    /// Allows trailing args
+    /// everything after the first value that is not an option, an option value or a command
+    /// is left untouched like after `--` and can be read with `trailing_args`, useful for wrapping other programs
+    /// # Arguments
+    /// * `data` - A boolean to allow trailing args
+    ///
+    /// # Example
+    /// ```
+    /// app.command("exec", "Run a program")
+    ///    .allow_trailing_args(true)
+    ///    .default(|x| fli_println!("running {:?}", x.trailing_args()));
+    /// // $ mytool exec -- cargo test --release
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn allow_trailing_args(&mut self, data: bool) -> &mut Self {
+        self.allow_trailing_args = data;
+        self
+    }
 
-    /// Adds a help option to the app
-    fn add_help_option(&mut self) {
-        self.option(
-            "-h --help",
-            &format!("print help screen for {}", self.name),
-            |x| x.default_help(),
-        );
+    /// Makes the last positional arg described with `argument` take every arg from its first value untouched, options included,
+    /// so `run <program> [args]...` works without `--`. The args before it are read as usual, and the captured ones
+    /// are both positional args and trailing args
+    /// # Arguments
+    /// * `data` - A boolean to capture the rest
+    ///
+    /// # Example
+    /// ```
+    /// app.command("run", "Run a program")
+    ///    .argument("<program>", "The program to run")
+    ///    .argument("[args]...", "The args of the program")
+    ///    .last_positional_captures_rest(true);
+    /// // $ mytool run cargo test --release   => positional args ["cargo", "test", "--release"]
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn last_positional_captures_rest(&mut self, data: bool) -> &mut Self {
+        self.last_positional_captures_rest = data;
+        self
     }
 
-    /// Add a version option to the app
-    fn add_version_option(&mut self) {
-        self.option(
-            "-v --version",
-            &format!("print version for {}", self.name),
-            |x| println!("{} Version: {}", x.name, x.version),
-        );
+    /// Reads the args starting with `-` that are not options of the command as values, for positional args
+    /// and option values like `-5d` or `-rf`. Off by default, so a mistyped option is an `UnknownOption` error
+    /// with the options it could be instead of a positional arg. Commands added after this use the same setting
+    /// # Arguments
+    /// * `data` - A boolean to allow values starting with `-`
+    ///
+    /// # Example
+    /// ```
+    /// app.command("exec", "Run a shell command")
+    ///    .allow_hyphen_values(true)
+    ///    .option("-c --command, <>", "The command", |x| {});
+    /// // $ mytool exec -c -rf   => command "-rf"
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn allow_hyphen_values(&mut self, data: bool) -> &mut Self {
+        self.allow_hyphen_values = data;
+        self
     }
 
-    /// 
-    pub fn print_help(&self, message: &str) {
-        println!(
-            "{0: <1} {1}",
-            "",
-            "ERROR================================".bold().red()
-        );
-        println!("{0: <5} {1}", "", message.bright_red());
-        println!(
-            "{0: <1} {1}",
-            "",
-            "================================".bold().red()
-        );
-        self.default_help();
-        process::exit(0);
+    /// Requires one of the commands to be passed, running without one is a `MissingSubcommand` error
+    /// `--help` and `--version` still work without a command
+    /// # Arguments
+    /// * `data` - A boolean to require a command
+    ///
+    /// # Example
+    /// ```
+    /// let remote = app.command("remote", "Manage remotes");
+    /// remote.subcommand_required(true);
+    /// remote.command("add", "Add a remote");
+    /// // $ app remote  => error: remote requires a command
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn subcommand_required(&mut self, data: bool) -> &mut Self {
+        self.subcommand_required = data;
+        self
     }
-    fn default_help(&self) {
-        println!("{0: <1} {1}: {2}", "", "Name".bold().green(), self.name);
-        println!("{0: <1} {1}: {2}", "", "Version".bold().green(), self.version);
-        println!(
-            "{0: <1} {1}: {2}",
-            "",
-            "Description".bold().blue(),
-            self.description
-        );
-        println!(
-            "{0: <1} {1}: {2} [options|commands]",
-            "",
-            "Usage".bold().yellow(),
-            self.name
-        );
-        self.print_options();
-        self.print_commands();
-        process::exit(0);
+
+    /// Sets the fewest positional args the app or command needs, passing fewer is a `MissingArguments` error
+    /// with the usage line, so callbacks do not have to check them. See `max_positional_args` for what positional args are
+    /// # Arguments
+    /// * `min` - The fewest positional args
+    ///
+    /// # Example
+    /// ```
+    /// app.command("cp", "Copy files").min_positional_args(2);
+    /// // $ app cp a.txt  => error: cp expects at least 2 arguments, got 1 (usage: cp [options|commands] <arg> <arg> [arg]...)
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn min_positional_args(&mut self, min: usize) -> &mut Self {
+        self.min_positional_args = min;
+        self
     }
 
-    pub fn print_most_similar_commands(&self, command: &str) {
-        let similar_commands = self.get_most_similar_commands(command);
-        if similar_commands.len() > 0 {
-            println!("{0: <1} {1}", "", "Did you mean:".bold().red());
-            for i in similar_commands {
-                //  give about 2 tap space then a bullet point before showing the similar command
-                println!("{0: <4} {1} {2}", "   ", "•".bold().red(), i.bold());
-            }
-        }
+    /// Sets the most positional args the app or command takes, passing more is a `TooManyArguments` error
+    /// naming the first one too many. Positional args are the values that are not options, option values or commands,
+    /// the trailing args included
+    /// # Arguments
+    /// * `max` - The most positional args
+    ///
+    /// # Example
+    /// ```
+    /// app.command("cat", "Print a file").max_positional_args(1);
+    /// // $ app cat a.txt b.txt  => error: Unexpected argument b.txt : expected at most 1, got 2
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn max_positional_args(&mut self, max: usize) -> &mut Self {
+        self.max_positional_args = Some(max);
+        self
     }
 
-    fn get_most_similar_commands(&self, command: &str) -> Vec<String> {
-        //  get commands with distances less than 3
-        let mut similar_commands: Vec<String> = vec![];
-        for key in self.help_hash_table.keys() {
-            let distance = levenshtein_distance(&command, key);
-            if distance < 3 {
-                similar_commands.push(key.to_string());
-            }
-        }
-        return similar_commands;
+    /// Describes a positional arg, listed under `Arguments:` in the help screen and in the generated usage line
+    /// # Arguments
+    /// * `name` - The name in a template like options, `<source>` required, `[dest]` optional
+    ///   and `<sources>...` or `[sources]...` for more than one
+    /// * `description` - The description of the arg
+    ///
+    /// # Example
+    /// ```
+    /// app.command("cp", "Copy files")
+    ///     .argument("<source>...", "The files to copy")
+    ///     .argument("<dest>", "Where to copy them");
+    /// // Usage: cp [options|commands] <source>... <dest>
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn argument(&mut self, name: &str, description: &str) -> &mut Self {
+        self.arguments.push((name.trim().to_string(), description.to_string()));
+        self
     }
 
-    fn print_options(&self) {
-        println!("{0: <1} {1}", "", "Options:".bold().blue());
-        println!(
-            "{0: <2}  {1: <12} | {2: <10} | {3: <10} | {4: <10}",
-            "",
-            "Long".bold().blue(),
-            "Short".bold().green(),
-            "ParamType",
-            "Description".bold().yellow()
-        );
-        for key in self.help_hash_table.keys() {
-            // if a command skip
-            if self.cammands_hash_tables.contains_key(key) {
-                continue;
-            }
-            if let Some(description) = self.help_hash_table.get(key) {
-                let mut short = String::new();
-                if let Some(short_key) = key.split(" ").collect::<Vec<&str>>().get(0) {
-                    short = short_key.to_string();
-                }
-                let mut param_type = String::new();
-                if let Some(param_d) = key.split(" ").collect::<Vec<&str>>().get(2) {
-                    param_type = match param_d.trim() {
-                        "<>" => "Required",
-                        "[]" => "Optional",
-                        "<...>" => "Required Multiple",
-                        "[...]" => "Optional Multiple",
-                        _ => "None",
-                    }
-                    .to_string();
-                }
-                let mut long = String::new();
-                if let Some(long_key) = key.split(" ").collect::<Vec<&str>>().get(1) {
-                    long = String::from(long_key.to_owned());
-                }
-                println!(
-                    "{0: <2}  {1: <12} | {2: <10} | {3: <10} | {4: <10}",
-                    "",
-                    long.blue(),
-                    short.green(),
-                    param_type,
-                    description.yellow()
-                );
-            }
-        }
+    /// Only reads the first positional arg as a command, the values after it are positional args even when
+    /// they are the name of a command, so commands taking free-form text are not taken over by their commands
+    /// # Arguments
+    /// * `data` - A boolean to prefer positional args
+    ///
+    /// # Example
+    /// ```
+    /// app.command("note", "Manage notes")
+    ///     .prefer_positionals_after_args(true)
+    ///     .command("list", "List the notes");
+    /// // $ app note list          => runs `note list`
+    /// // $ app note buy list      => runs `note` with the positional args ["buy", "list"]
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn prefer_positionals_after_args(&mut self, data: bool) -> &mut Self {
+        self.prefer_positionals_after_args = data;
+        self
     }
-    fn print_commands(&self) {
-        println!("{0: <1} {1}", "", "Commands:".bold().blue());
-        println!(
-            "{0: <2} {1: <12} | {2: <10}",
-            "",
-            "Name".bold().blue(),
-            "Description".bold().yellow()
-        );
-        for key in self.help_hash_table.keys() {
-            // if a command skip
-            if !self.cammands_hash_tables.contains_key(key) {
-                continue;
-            }
-            if let Some(description) = self.help_hash_table.get(key) {
-                println!(
-                    "{0: <2} {1: <12} | {2: <10}",
-                    "",
-                    key.blue(),
-                    description.yellow()
-                );
-            }
-        }
+
+    /// Prints the help screen when no arg is passed, instead of running the default callback
+    /// # Arguments
+    /// * `data` - A boolean to print the help screen
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn arg_required_else_help(&mut self, data: bool) -> &mut Self {
+        self.arg_required_else_help = data;
+        self
     }
-    pub fn default(&mut self, callback: fn(app: &Self)) -> &mut Self {
-        self.default_callback = callback;
-        return self;
+
+    /// Allows options the app does not have, they are collected in `unknown_args` instead of being an error
+    /// useful for apps forwarding leftovers to another program or parsing them again
+    /// # Arguments
+    /// * `data` - A boolean to allow unknown options
+    ///
+    /// # Example
+    /// ```
+    /// app.allow_unknown_args(true);
+    /// app.default(|x| fli_println!("forwarding {:?}", x.unknown_args()));
+    /// // $ app --jobs=4 -x  => forwarding ["--jobs=4", "-x"]
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn allow_unknown_args(&mut self, data: bool) -> &mut Self {
+        self.unknown_options = match data {
+            true => UnknownOptionPolicy::Collect,
+            false => UnknownOptionPolicy::Error,
+        };
+        self
     }
 
-    pub fn option(&mut self, key: &str, description: &str, value: fn(app: &Self)) -> &mut Self {
-        let args: Vec<&str> = key.split(",").collect();
-        let mut options = String::new();
-        if let Some(opts) = args.get(0) {
-            options = String::from(opts.to_owned());
-        }
-        let broken_args: Vec<_> = options.split(" ").collect();
-        let short = broken_args[0].trim();
-        let mut long = broken_args[0].trim();
-        if broken_args.len() > 1 {
-            long = broken_args[1].trim();
-            self.short_hash_table
-                .insert(short.to_string(), long.to_string());
-        }
-        // for i in options.split(" ") {
-        let mut param_type = String::new();
-        if let Some(param_d) = args.get(1) {
-            param_type = String::from(param_d.to_owned());
-        }
-        if args.len() > 1 && ["<>", "[]", "<...>", "[...]"].contains(&param_type.trim()) == false {
+    /// Sets what to do with options the app or command does not have, commands added later get the same policy
+    /// # Arguments
+    /// * `policy` - `Error` (the default), `Warn` to print a warning and ignore them or `Collect` like `allow_unknown_args`
+    ///
+    /// # Example
+    /// ```
+    /// app.command("exec", "Run a program")
+    ///     .set_unknown_option_policy(UnknownOptionPolicy::Warn);
+    /// // $ app exec -x  => warning: ignoring unknown option -x
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn set_unknown_option_policy(&mut self, policy: UnknownOptionPolicy) -> &mut Self {
+        self.unknown_options = policy;
+        self
+    }
+
+    /// Allows response files, an `@file` arg is replaced by the args in the file before parsing
+    /// for command lines too long for the shell. The args in the file are split like a shell would,
+    /// so quotes keep spaces, and can be `@file` args too. Args after `--` are kept as is
+    /// # Arguments
+    /// * `data` - A boolean to allow response files
+    ///
+    /// # Example
+    /// ```
+    /// app.allow_response_files(true);
+    /// // $ app build @args.txt
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn allow_response_files(&mut self, data: bool) -> &mut Self {
+        self.response_files = data;
+        self
+    }
+
+    /// Allows Windows style options along the usual ones, for tools replacing legacy Windows utilities.
+    /// `/r` is read as `-r`, `/output` as `--output`, `/output:file.txt` or `/output=file.txt` as `--output file.txt`
+    /// and `/?` as `--help`. Only args naming an option of the command are read this way, so paths like `/tmp` are values.
+    /// Commands added after this use the same setting
+    /// # Arguments
+    /// * `data` - A boolean to allow `/` options
+    ///
+    /// # Example
+    /// ```
+    /// app.allow_slash_options(true);
+    /// app.option("-r --recursive", "Copy directories", |x| {});
+    /// app.option("-o --output, <>", "Where to copy", |x| {});
+    /// // $ app /r /output:backup   => runs --recursive and --output backup
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn allow_slash_options(&mut self, data: bool) -> &mut Self {
+        self.slash_options = data;
+        self
+    }
+
+    /// Reports every error in the args at once (unknown options, missing and invalid values, positional args
+    /// and validators) instead of stopping at the first one, so users do not fix them one run at a time.
+    /// More than one error is a `FliError::Multiple`. Commands added after this use the same setting
+    /// # Arguments
+    /// * `data` - A boolean to collect the errors
+    ///
+    /// # Example
+    /// ```
+    /// app.collect_errors(true);
+    /// app.option("-p --port, <>", "The port", |x| {});
+    /// // $ app --prot 80 --port   => reports both "Unknown option --prot" and "Invalid syntax : --port does not have a value"
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn collect_errors(&mut self, data: bool) -> &mut Self {
+        self.collect_errors = data;
+        self
+    }
+
+    /// Sets the command to run when none is passed, it gets all the args
+    /// options of the app itself (like `--help` or `--version`) still run on the app
+    /// # Arguments
+    /// * `name` - The name of the command
+    ///
+    /// # Example
+    /// ```
+    /// app.command("serve", "Start the server").option("-p --port, <>", "The port", |x| {});
+    /// app.default_command("serve");
+    /// // $ app -p 8080  => same as `app serve -p 8080`
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn default_command(&mut self, name: &str) -> &mut Self {
+        self.default_command = Some(name.to_string());
+        self
+    }
+
+    /// Allows long options to be shortened to any unique prefix, like GNU tools
+    /// `--verb` is read as `--verbose`, a prefix matching more than one option is an error
+    /// # Arguments
+    /// * `data` - A boolean to allow abbreviations
+    ///
+    /// # Example
+    /// ```
+    /// app.allow_abbreviations(true);
+    /// app.option("--verbose", "Print more", |x| {});
+    /// app.option("--version", "Print the version", |x| {});
+    /// // $ app --verb  => runs --verbose
+    /// // $ app --ver   => error: could be --verbose or --version
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn allow_abbreviations(&mut self, data: bool) -> &mut Self {
+        self.allow_abbreviations = data;
+        self
+    }
+
+    /// Allows `_` and `-` to be swapped in long options, so `--max_depth` is read as `--max-depth` and the other way around
+    /// commands added after this use the same setting
+    /// # Arguments
+    /// * `data` - A boolean to allow either separator
+    ///
+    /// # Example
+    /// ```
+    /// app.allow_any_separator(true);
+    /// app.option("--max-depth, <>", "How deep to search", |x| {});
+    /// // $ app --max_depth 2  => runs --max-depth
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn allow_any_separator(&mut self, data: bool) -> &mut Self {
+        self.allow_any_separator = data;
+        self
+    }
+
+    /// Shows help screens taller than the terminal through `$PAGER` (or `less -R`), like git does
+    /// only when writing to a terminal, the help is printed as usual otherwise
+    ///
+    /// # Example
+    /// ```
+    /// let mut app : Fli = Fli::init("name", "a sample app");
+    /// app.enable_help_pager();
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn enable_help_pager(&mut self) -> &mut Self {
+        self.help_pager = true;
+        self
+    }
+
+    /// Sets the layout of the help screen
+    /// the placeholders `{name}`, `{version}`, `{description}`, `{usage}`, `{arguments}`, `{options}` and `{subcommands}`
+    /// are replaced by their section, an empty section takes no line. Commands without their own template use their parent's
+    /// # Arguments
+    /// * `template` - The layout, see `DEFAULT_HELP_TEMPLATE` for the default one
+    ///
+    /// # Example
+    /// ```
+    /// let mut app : Fli = Fli::init("name", "a sample app");
+    /// app.set_help_template("{usage}\n\n{subcommands}\n{options}\nMade with fli");
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn set_help_template(&mut self, template: &str) -> &mut Self {
+        self.help_template = Some(template.to_string());
+        self
+    }
+
+    /// Replaces the whole help screen of the app and its commands, commands without their own renderer use their parent's
+    /// # Arguments
+    /// * `renderer` - The renderer, see `HelpRenderer`
+    ///
+    /// # Example
+    /// ```
+    /// app.set_help_renderer(Box::new(ManPageHelp));
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn set_help_renderer(&mut self, renderer: Box<dyn HelpRenderer>) -> &mut Self {
+        self.help_renderer = Some(Rc::from(renderer));
+        self
+    }
+
+    /// Replaces how the errors `run` stops on are shown for the app and its commands,
+    /// commands without their own renderer use their parent's. `on_error` handlers still take the errors first
+    /// # Arguments
+    /// * `renderer` - The renderer, see `ErrorRenderer`
+    ///
+    /// # Example
+    /// ```
+    /// app.set_error_renderer(Box::new(JsonErrors));
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn set_error_renderer(&mut self, renderer: Box<dyn ErrorRenderer>) -> &mut Self {
+        self.error_renderer = Some(Rc::from(renderer));
+        self
+    }
+
+    /// Replaces the usage line of the help screen, `<name> [options|commands]` by default
+    /// # Arguments
+    /// * `usage` - The usage pattern
+    ///
+    /// # Example
+    /// ```
+    /// let mut app : Fli = Fli::init("myapp", "a sample app");
+    /// app.command("cp", "Copy files").set_usage("myapp cp [options] <source>... <dest>");
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn set_usage(&mut self, usage: &str) -> &mut Self {
+        self.usage = Some(usage.to_string());
+        self
+    }
+
+    /// Marks an option as deprecated, it keeps working but prints a warning when used
+    /// the warning is hidden when `--quiet` is passed (if the app has that option) or with `display::set_quiet`
+    /// # Arguments
+    /// * `option` - The option (`-o`, `--old`, `o` or `old`)
+    /// * `hint` - What to use instead
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-o --old, <>", "The old way", |x| {});
+    /// app.deprecate_option("--old", "use --new instead");
+    /// // $ app --old value
+    /// // Warning: --old is deprecated, use --new instead
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn deprecate_option(&mut self, option: &str, hint: &str) -> &mut Self {
+        self.deprecated_hash_table
+            .insert(self.get_callable_name(option.to_string()), hint.to_string());
+        self
+    }
+
+    /// Marks a command as deprecated, it keeps working but prints a warning when used
+    /// # Arguments
+    /// * `name` - The name of the command
+    /// * `hint` - What to use instead
+    ///
+    /// # Example
+    /// ```
+    /// app.command("rm", "Remove files");
+    /// app.deprecate_command("rm", "use 'delete' instead");
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn deprecate_command(&mut self, name: &str, hint: &str) -> &mut Self {
+        self.deprecated_hash_table
+            .insert(name.to_string(), hint.to_string());
+        self
+    }
+
+    /// Adds a hidden alias to an option, it works like the option but is not listed in the help screen or completions
+    /// so a renamed option keeps working for scripts, see `deprecate_alias` to warn when it is used
+    /// # Arguments
+    /// * `option` - The option (`-c`, `--color`, `c` or `color`), it must already be added
+    /// * `alias` - The alias as passed, like `--colour` or `-C`
+    ///
+    /// # Example
+    /// ```
+    /// app.option("--dry-run", "Do nothing", |x| {});
+    /// app.hidden_option_alias("--dry-run", "--dryrun");
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn hidden_option_alias(&mut self, option: &str, alias: &str) -> &mut Self {
+        let long = self.get_callable_name(option.to_string());
+        self.option_names.insert(alias.to_string(), long);
+        self
+    }
+
+    /// Adds a hidden alias to a command, it runs the command but is not listed in the help screen or completions
+    /// # Arguments
+    /// * `command` - The name of the command
+    /// * `alias` - The alias, like the old name of the command
+    ///
+    /// # Example
+    /// ```
+    /// app.command("delete", "Remove files");
+    /// app.hidden_command_alias("delete", "rm");
+    /// app.deprecate_alias("rm", "use 'delete' instead");
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn hidden_command_alias(&mut self, command: &str, alias: &str) -> &mut Self {
+        self.command_aliases.insert(alias.to_string(), command.to_string());
+        self
+    }
+
+    /// Prints a warning when a hidden alias is used, the option or command it stands for stays as it is
+    /// # Arguments
+    /// * `alias` - The alias of an option or command, as added
+    /// * `hint` - What to use instead
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn deprecate_alias(&mut self, alias: &str, hint: &str) -> &mut Self {
+        self.deprecated_hash_table
+            .insert(alias.to_string(), hint.to_string());
+        self
+    }
+
+    /// Gets a command by its name or one of its hidden aliases
+    fn find_command(&self, name: &str) -> Option<&Fli> {
+        let name = self.command_aliases.get(name).map_or(name, String::as_str);
+        self.cammands_hash_tables.get(name)
+    }
+
+    /// Prints the deprecation warning of an option or command if it is deprecated
+    fn warn_if_deprecated(&self, name: &str) {
+        let Some(hint) = self.deprecated_hash_table.get(name) else {
+            return;
+        };
+        if !self.is_registered_and_passed("--quiet") {
+            display::print_warning(&fill(
+                &messages::messages().deprecated,
+                &[("name", name), ("hint", hint)],
+            ));
+        }
+    }
+
+    /// Sets the colors and symbols of the output, applied when the app runs
+    /// commands without their own theme use their parent's
+    /// # Arguments
+    /// * `theme` - The theme, see `display::Theme`
+    ///
+    /// # Example
+    /// ```
+    /// app.set_theme(Theme {
+    ///     header: Color::Magenta,
+    ///     ..Theme::default()
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn set_theme(&mut self, theme: Theme) -> &mut Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Sets the text of the output: help screen headers, errors, warnings and prompts, applied when the app runs
+    /// commands without their own messages use their parent's
+    /// # Arguments
+    /// * `messages` - The messages, see `messages::Messages`
+    ///
+    /// # Example
+    /// ```
+    /// app.set_messages(Messages {
+    ///     options_header: "Flags".to_string(),
+    ///     ..Messages::default()
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn set_messages(&mut self, messages: Messages) -> &mut Self {
+        self.messages = Some(messages);
+        self
+    }
+
+    /// Sets the text of the output to the built-in messages of a locale, English when there are none for it
+    /// # Arguments
+    /// * `locale` - The locale like `fr` or `es_MX.UTF-8`, see `Messages::for_locale`
+    ///
+    /// # Example
+    /// ```
+    /// app.set_locale(&env::var("LANG").unwrap_or_default());
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn set_locale(&mut self, locale: &str) -> &mut Self {
+        self.set_messages(Messages::for_locale(locale))
+    }
+
+    /// Makes the output only use ASCII symbols (`*`, `+`, `->` instead of `•`, `✓`, `→`), for terminals and logs that garble unicode
+    /// when not called it is detected from the environment, see `display::is_ascii_output`
+    /// # Arguments
+    /// * `ascii_output` - Whether to only use ASCII symbols
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn ascii_output(&mut self, ascii_output: bool) -> &mut Self {
+        self.ascii_output = Some(ascii_output);
+        self
+    }
+
+    /// Lists an option under its own header in the help screen, with the other options of the same group
+    /// options without a group are listed under `Options:`
+    /// # Arguments
+    /// * `group` - The name of the group, like `Output options`
+    /// * `option` - The option (`-l`, `--long`, `l` or `long`)
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-l --long", "Use a long listing format", |x| {});
+    /// app.option("-a --all", "Do not ignore entries starting with .", |x| {});
+    /// app.add_option_to_group("Output options", "--long");
+    /// app.add_option_to_group("Filtering", "--all");
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn add_option_to_group(&mut self, group: &str, option: &str) -> &mut Self {
+        let long = self.get_callable_name(option.to_string());
+        self.option_groups.insert(long, group.to_string());
+        self
+    }
+
+    /// Sets the priority of the callback of an option, the callbacks of the passed options run from the highest priority
+    /// to the lowest, in the order they were passed for the same priority (0 for options without one)
+    ///
+    /// `--help` has `HELP_PRIORITY` and `--version` has `VERSION_PRIORITY`, so they run before the other options
+    /// and the help screen wins when both are passed, whatever the order
+    /// # Arguments
+    /// * `option` - The option (`-l`, `--long`, `l` or `long`)
+    /// * `priority` - The priority, higher runs first
+    ///
+    /// # Example
+    /// ```
+    /// app.option("--changelog", "Print the changelog and quit", |x| {
+    ///     fli_println!("{}", include_str!("../CHANGELOG.md"));
+    ///     x.request_exit(0);
+    /// });
+    /// // wins over `--version`, loses to `--help`
+    /// app.set_option_priority("--changelog", VERSION_PRIORITY + 1);
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn set_option_priority(&mut self, option: &str, priority: i32) -> &mut Self {
+        let long = self.get_callable_name(option.to_string());
+        self.option_priorities.insert(long, priority);
+        self
+    }
+
+    /// Sets a name to look an option up by, for options whose name differs from their long flag
+    /// the name is checked before the flags, so every getter taking an option accepts it
+    /// # Arguments
+    /// * `option` - The option (`-H`, `--human-readable`, `H` or `human-readable`)
+    /// * `name` - The name, without dashes
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-H --human-readable", "Print sizes like 1K and 2M", |x| {});
+    /// app.set_option_name("--human-readable", "human");
+    /// app.default(|x| {
+    ///     if x.is_passed("human".to_string()) {
+    ///         fli_println!("1K");
+    ///     }
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn set_option_name(&mut self, option: &str, name: &str) -> &mut Self {
+        let long = self.get_callable_name(option.to_string());
+        self.option_names.insert(name.to_string(), long);
+        self
+    }
+
+    /// Sets the order options and commands are listed in on the help screen, the order they were added by default
+    /// commands without their own order use their parent's
+    /// # Arguments
+    /// * `order` - `HelpOrder::Declaration` or `HelpOrder::Alphabetical`
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn set_help_order(&mut self, order: HelpOrder) -> &mut Self {
+        self.help_sort = Some(order);
+        self
+    }
+
+    /// Adds an entry to the help screen, keeping the order entries are added in
+    fn insert_help(&mut self, key: String, description: String) {
+        if self.help_hash_table.insert(key.clone(), description).is_none() {
+            self.help_order.push(key);
+        }
+    }
+
+    /// Removes an entry from the help screen
+    fn remove_help(&mut self, key: &str) -> Option<String> {
+        self.help_order.retain(|entry| entry != key);
+        self.help_hash_table.remove(key)
+    }
+
+    /// Gets the keys of the help screen entries in the order set with `set_help_order`
+    fn help_keys(&self) -> Vec<&String> {
+        let mut keys: Vec<&String> = self.help_order.iter().collect();
+        if self.help_sort == Some(HelpOrder::Alphabetical) {
+            // options by long name, `-n --name <>` by `name`
+            keys.sort_by_key(|key| {
+                let name = key.split_whitespace().nth(1).unwrap_or(key);
+                name.trim_start_matches('-').to_string()
+            });
+        }
+        keys
+    }
+
+    /// Lists the command under its own header in the help screen of its parent, with the other commands of the same category
+    /// commands without a category are listed under `Commands:`
+    /// # Arguments
+    /// * `name` - The name of the category, like `File operations`
+    ///
+    /// # Example
+    /// ```
+    /// app.command("cp", "Copy files").category("File operations");
+    /// app.command("mv", "Move files").category("File operations");
+    /// app.command("login", "Log in").category("Account");
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn category(&mut self, name: &str) -> &mut Self {
+        self.category = Some(name.to_string());
+        self
+    }
+
+    /// Sets text to print above the help screen, like a banner
+    /// # Arguments
+    /// * `text` - The text to print
+    ///
+    /// # Example
+    /// ```
+    /// let mut app : Fli = Fli::init("name", "a sample app");
+    /// app.before_help("name - the friendly sample app");
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn before_help(&mut self, text: &str) -> &mut Self {
+        self.before_help = Some(text.to_string());
+        self
+    }
+
+    /// Sets text to print below the help screen, like licensing or links to docs
+    /// # Arguments
+    /// * `text` - The text to print
+    ///
+    /// # Example
+    /// ```
+    /// let mut app : Fli = Fli::init("name", "a sample app");
+    /// app.after_help("Docs: https://docs.rs/fli");
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn after_help(&mut self, text: &str) -> &mut Self {
+        self.after_help = Some(text.to_string());
+        self
+    }
+
+    /// Sets a longer description of the app or command, with details or examples, shown by `--help`
+    /// while `-h` keeps to the one-line description
+    /// # Arguments
+    /// * `text` - The long description
+    ///
+    /// # Example
+    /// ```
+    /// app.command("sync", "Sync the files")
+    ///     .long_help("Sync the files with the server.\n  Example: app sync --dry-run");
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn long_help(&mut self, text: &str) -> &mut Self {
+        self.long_help = Some(text.to_string());
+        self
+    }
+
+    /// Sets a longer description of an option shown by `--help`, `-h` shows its description
+    /// # Arguments
+    /// * `option` - The option (`-o`, `--output`, `o` or `output`)
+    /// * `text` - The long description
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn option_long_help(&mut self, option: &str, text: &str) -> &mut Self {
+        let long = self.get_callable_name(option.to_string());
+        self.option_long_helps.insert(long, text.to_string());
+        self
+    }
+
+    /// Checks if the help screen being rendered is the long one `--help` prints, rather than the short one of `-h`
+    /// for help renderers showing more in the long one
+    ///
+    /// # Returns
+    /// * `bool` - Whether it is the long help screen
+    pub fn is_long_help(&self) -> bool {
+        self.show_long_help
+    }
+
+
+    /// Adds a help option to the app
+    fn add_help_option(&mut self) {
+        self.option(
+            "-h --help",
+            &fill(&Messages::english().help_option, &[("name", &self.name)]),
+            help_callback,
+        );
+        self.option_priorities.insert("--help".to_string(), HELP_PRIORITY);
+    }
+
+    /// Adds a `help` command to the app, printing the help screen of the commands named after it
+    /// like `--help` does, as in git and cargo
+    ///
+    /// # Example
+    /// ```
+    /// app.add_help_command();
+    /// app.command_at("remote add", "Add a remote");
+    /// // $ app help remote add   => the help screen of `remote add`, same as `app remote add --help`
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn add_help_command(&mut self) -> &mut Self {
+        let description = fill(&Messages::english().help_command, &[("name", &self.name)]);
+        self.command("help", &description).default(help_command_callback);
+        self
+    }
+
+    /// Add a version option to the app, it prints `name version` and stops like the help option
+    fn add_version_option(&mut self) {
+        self.option(
+            "-V --version",
+            &fill(&Messages::english().version_option, &[("name", &self.name)]),
+            version_callback,
+        );
+        self.option_priorities.insert("--version".to_string(), VERSION_PRIORITY);
+        // `-v` was its short name before, it stays until the app gives `-v` to another option
+        self.hidden_option_alias("--version", "-v");
+    }
+
+    /// Adds the `-v --verbose` (repeat it for more) and `-q --quiet` options to the app and all its commands,
+    /// `verbosity` is read from them and `display` hides info and warnings as it goes down
+    /// `-v` stops being a hidden alias of `--version`
+    ///
+    /// # Example
+    /// ```
+    /// app.add_verbosity_flags();
+    /// app.command("build", "Build the project").default(|x| {
+    ///     if x.verbosity() > 0 {
+    ///         fli_println!("building in {}", env::current_dir().unwrap().display());
+    ///     }
+    /// });
+    /// // $ app -v build   => verbosity 1, also when passed after the command
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn add_verbosity_flags(&mut self) -> &mut Self {
+        self.verbosity_flags = true;
+        // they only change the verbosity, running the default callback when passed alone
+        let english = Messages::english();
+        self.option("-v --verbose", &english.verbose_option, |_x| {});
+        self.option("-q --quiet", &english.quiet_option, |_x| {});
+        for command in self.cammands_hash_tables.values_mut() {
+            command.add_verbosity_flags();
+        }
+        self
+    }
+
+    /// Gets how much output is wanted, 0 by default
+    /// +1 for each `--verbose`, at least 2 with `--debug` and -1 for each `--quiet` passed (if the app has those options),
+    /// including the ones passed to the parents of the command
+    ///
+    /// # Returns
+    /// * `i8` - The verbosity, also set as `display::verbosity` while the callbacks run
+    pub fn verbosity(&self) -> i8 {
+        self.parent_verbosity.saturating_add(self.passed_verbosity(self.parsed_args()))
+    }
+
+    /// Adds a `--config <>` option to the app and all its commands, the options not passed on the command line
+    /// take their value from that file (or `default_path` when not passed, skipped if it does not exist)
+    ///
+    /// the top level keys are options of the app and tables are the options of its commands, by long name without the dashes.
+    /// `.toml`, `.json` and `.yaml` files are read with the `toml`, `json` and `yaml` features
+    /// # Arguments
+    /// * `default_path` - The config file to read when `--config` is not passed
+    ///
+    /// # Example
+    /// ```
+    /// app.enable_config_file("myapp.toml");
+    /// app.option("-n --name, <>", "Your name", |x| {});
+    /// app.command("serve", "Serve the app").option("-p --port, <>", "The port", |x| {});
+    /// // myapp.toml
+    /// // name = "james"
+    /// //
+    /// // [serve]
+    /// // port = 8080
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn enable_config_file(&mut self, default_path: &str) -> &mut Self {
+        self.config_file = Some(default_path.to_string());
+        self.option(
+            "--config, <>",
+            &fill(&Messages::english().config_option, &[("path", default_path)]),
+            |_x| {},
+        );
+        for command in self.cammands_hash_tables.values_mut() {
+            command.enable_config_file(default_path);
+        }
+        self
+    }
+
+    /// Loads the `.env` file of the current directory (or the closest parent that has one) into the environment
+    /// before the args are parsed, variables already set are kept. Needs the `dotenv` feature
+    ///
+    /// # Example
+    /// ```
+    /// app.load_dotenv();
+    /// app.option("-t --token, <>", "The API token, API_TOKEN by default", |x| {
+    ///     let token = x.get_values("token".to_string())
+    ///         .map(|values| values[0].clone())
+    ///         .or_else(|_| env::var("API_TOKEN"));
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    #[cfg(feature = "dotenv")]
+    pub fn load_dotenv(&mut self) -> &mut Self {
+        self.dotenv_file = Some(String::new());
+        self
+    }
+
+    /// Like `load_dotenv`, loading the given file instead of `.env`
+    /// # Arguments
+    /// * `path` - The file to load, skipped if it does not exist
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    #[cfg(feature = "dotenv")]
+    pub fn load_dotenv_from(&mut self, path: &str) -> &mut Self {
+        self.dotenv_file = Some(path.to_string());
+        self
+    }
+
+    /// Loads the `.env` file set with `load_dotenv`, warning when it can not be read
+    #[cfg(feature = "dotenv")]
+    fn apply_dotenv(&self) {
+        let Some(path) = &self.dotenv_file else {
+            return;
+        };
+        let loaded = match path.as_str() {
+            "" => dotenvy::dotenv().map(|_| ()),
+            path => dotenvy::from_path(path),
+        };
+        match loaded {
+            Err(error) if !error.not_found() => {
+                display::print_warning(&format!("could not load the .env file : {error}"))
+            }
+            _ => {}
+        }
+    }
+
+    /// Like `enable_config_file`, the default path being `config.toml` in the config directory of the app,
+    /// like `~/.config/<app>/config.toml` (see `paths::config_dir`)
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn enable_default_config_file(&mut self) -> &mut Self {
+        let path = self.config_dir().unwrap_or_default().join("config.toml");
+        self.enable_config_file(&path.to_string_lossy())
+    }
+
+    /// Gets the directory the config files of the app go in, see `paths::config_dir`
+    pub fn config_dir(&self) -> Option<PathBuf> {
+        paths::config_dir(&self.name)
+    }
+
+    /// Gets the directory the cache files of the app go in, see `paths::cache_dir`
+    pub fn cache_dir(&self) -> Option<PathBuf> {
+        paths::cache_dir(&self.name)
+    }
+
+    /// Gets the directory the data files of the app go in, see `paths::data_dir`
+    pub fn data_dir(&self) -> Option<PathBuf> {
+        paths::data_dir(&self.name)
+    }
+
+    /// Loads the config file once, from the `--config` option or the default path of `enable_config_file`
+    /// # Returns
+    /// * `Result<Option<&config::Section>, FliError>` - The section of this command, none if there is none or no config file
+    fn load_config(&self) -> Result<Option<&config::Section>, FliError> {
+        let Some(default_path) = &self.config_file else {
+            return Ok(None);
+        };
+        let loaded = self.config.get_or_init(|| match self.get_values("--config".to_string()) {
+            Ok(values) => config::load(&values[0], true),
+            Err(_) => config::load(default_path, false),
+        });
+        match loaded {
+            Ok(section) => Ok(section.as_ref().and_then(|section| section.get(&self.config_section))),
+            Err(message) => Err(FliError::InvalidValue {
+                option: "--config".to_string(),
+                message: message.to_string(),
+                index: self.get_arg_index("--config").map_or(0, |index| index + 1),
+            }),
+        }
+    }
+
+    /// Gets the values the config file gives an option, like `--name`
+    fn get_config_values(&self, arg_name: &str) -> Option<Vec<String>> {
+        if arg_name == "--config" {
+            return None;
+        }
+        let section = self.load_config().ok()??;
+        section.values.get(arg_name.trim_start_matches("--")).cloned()
+    }
+
+    /// Checks if the config file sets an option, flags set to `false` are not
+    fn is_set_in_config(&self, arg_name: &str) -> bool {
+        match self.get_config_values(arg_name) {
+            Some(values) if self.get_param_type(arg_name) == Some("") => values != ["false"],
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    /// Add a color option to the app, it is applied before any callback runs
+    fn add_color_option(&mut self) {
+        self.option(
+            "--color, <>",
+            &Messages::english().color_option,
+            |_x| {},
+        );
+        self.value_hint("--color", ValueHint::Choices(ColorPolicy::completion_hints()));
+        self.builtin_color = true;
+    }
+
+    /// Applies the `--color` option if it was passed
+    fn apply_color_option(&self) -> Result<(), FliError> {
+        if !self.builtin_color {
+            return Ok(());
+        }
+        let value = match self.get_values("--color".to_string()) {
+            Ok(values) => values[0].to_owned(),
+            Err(_) => return Ok(()),
+        };
+        let policy = ColorPolicy::parse(&value).map_err(|message| FliError::InvalidValue {
+            option: "--color".to_string(),
+            message,
+            index: self.get_arg_index("--color").map_or(0, |index| index + 1),
+        })?;
+        display::set_color_policy(policy);
+        Ok(())
+    }
+
+    /// Prints an error message followed by the help screen, then exits
+    pub fn print_help(&self, message: &str) {
+        self.print_help_and_stop(message);
+        exit(0);
+    }
+
+    /// Prints an error message followed by the help screen, then stops the callbacks like the help option
+    pub(crate) fn print_help_and_stop(&self, message: &str) {
+        self.print_error_banner(message);
+        self.default_help();
+    }
+
+    /// Skips the callbacks left once the running one returns, then `run` exits with the code
+    /// and `try_run` returns, unlike `fli::exit` it does not end the process when the app is embedded or in a REPL
+    /// # Arguments
+    /// * `code` - The code `run` exits with
+    ///
+    /// # Example
+    /// ```
+    /// app.option("--list-themes", "List the themes and quit", |x| {
+    ///     fli_println!("dark light");
+    ///     x.request_exit(0);
+    /// });
+    /// ```
+    pub fn request_exit(&self, code: i32) {
+        self.action.set(Action::Exit(code));
+    }
+
+    /// Prints the error with the error renderer (through the pager when enabled), then exits with the
+    /// exit code of the error, or hands the error to the error handler and exits with the code it returns
+    fn report_error(&self, error: &FliError) {
+        if self.error_handler.is_some() {
+            exit(self.error_exit_code(error));
+        }
+        let rendered = match &self.error_renderer {
+            Some(renderer) => renderer.render(self, error),
+            None => DefaultErrorRenderer.render(self, error),
+        };
+        if !(self.help_pager && display::page(&rendered)) {
+            display::print(format_args!("{rendered}"));
+        }
+        exit(self.get_exit_code(error));
+    }
+
+    fn print_error_banner(&self, message: &str) {
+        let theme = display::theme();
+        fli_println!(
+            "{0: <1} {1}",
+            "",
+            format!("{}================================", messages::messages().error_banner)
+                .bold()
+                .color(theme.error)
+        );
+        fli_println!("{0: <5} {1}", "", message.color(theme.error));
+        fli_println!(
+            "{0: <1} {1}",
+            "",
+            "================================".bold().color(theme.error)
+        );
+    }
+    fn default_help(&self) {
+        self.show_help(&self.help_screen());
+        self.request_exit(0);
+    }
+
+    /// Prints a help screen, through the pager when enabled
+    fn show_help(&self, help: &str) {
+        if !(self.help_pager && display::page(help)) {
+            display::print(format_args!("{help}"));
+        }
+    }
+
+    /// Renders the help screen with the help renderer
+    fn help_screen(&self) -> String {
+        match &self.help_renderer {
+            Some(renderer) => renderer.render(self),
+            None => DefaultHelpRenderer.render(self),
+        }
+    }
+
+    /// Renders the long help screen `--help` prints
+    fn long_help_screen(&self) -> String {
+        let mut app = self.clone();
+        app.show_long_help = true;
+        app.help_screen()
+    }
+
+    /// Checks if the help was asked for with `--help` or another long help option rather than `-h`
+    fn long_help_requested(&self) -> bool {
+        self.parsed_args().iter().skip(1).any(|arg| {
+            let long = self.get_callable_name(arg.to_string());
+            arg.starts_with("--")
+                && self.is_option_token(arg)
+                && (long == "--help" || self.arg_actions.get(&long) == Some(&ArgAction::Help))
+        })
+    }
+
+    /// Renders the help screen of the app without printing it or exiting, for snapshot tests or showing it in a GUI
+    /// it is the exact text `--help` prints, with the theme and messages of the app
+    ///
+    /// # Example
+    /// ```
+    /// let help = app.render_help();
+    /// assert!(help.contains("Options:"));
+    /// ```
+    ///
+    /// # Returns
+    /// * `String` - The help screen
+    pub fn render_help(&self) -> String {
+        let _theme = display::scoped_theme(self.theme.clone());
+        let _messages = messages::scoped_messages(self.messages.clone());
+        self.long_help_screen()
+    }
+
+    /// Renders the short help screen `-h` prints like `render_help`, with the one-line descriptions
+    ///
+    /// # Returns
+    /// * `String` - The help screen
+    pub fn render_short_help(&self) -> String {
+        let _theme = display::scoped_theme(self.theme.clone());
+        let _messages = messages::scoped_messages(self.messages.clone());
+        self.help_screen()
+    }
+
+    /// Renders the help screen of a command like `render_help`, as `app <path> --help` prints it
+    /// # Arguments
+    /// * `path` - The names of the command and the commands it is under, like `remote add`
+    ///
+    /// # Example
+    /// ```
+    /// app.command_at("remote add", "Add a remote");
+    /// let help = app.render_help_for("remote add").unwrap();
+    /// ```
+    ///
+    /// # Returns
+    /// * `Option<String>` - The help screen, none if there is no command at the path
+    pub fn render_help_for(&self, path: &str) -> Option<String> {
+        let mut command = self.clone();
+        for name in path.split_whitespace() {
+            let mut next = command.find_command(name)?.clone();
+            next.inherit_help(&command);
+            command = next;
+        }
+        Some(command.render_help())
+    }
+
+    /// Takes the help screen settings the command does not set from its parent
+    fn inherit_help(&mut self, parent: &Fli) {
+        if self.help_template.is_none() {
+            self.help_template = parent.help_template.clone();
+        }
+        if self.help_renderer.is_none() {
+            self.help_renderer = parent.help_renderer.clone();
+        }
+        self.help_sort = self.help_sort.or(parent.help_sort);
+        if self.theme.is_none() {
+            self.theme = parent.theme.clone();
+        }
+        if self.messages.is_none() {
+            self.messages = parent.messages.clone();
+        }
+    }
+
+    /// Prints every section of the help screen, laid out by the help template
+    fn print_help_sections(&self) {
+        let template = self.help_template.as_deref().unwrap_or(DEFAULT_HELP_TEMPLATE);
+        if let Some(text) = &self.before_help {
+            fli_println!("{text}");
+        }
+        let mut help = String::new();
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            help.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let placeholder = match rest.find('}') {
+                Some(end) => &rest[..=end],
+                None => break,
+            };
+            rest = &rest[placeholder.len()..];
+            match self.render_help_section(placeholder) {
+                Some(section) if section.is_empty() => rest = rest.strip_prefix('\n').unwrap_or(rest),
+                Some(section) => help.push_str(&section),
+                None => help.push_str(placeholder),
+            }
+        }
+        help.push_str(rest);
+        fli_println!("{help}");
+        if let Some(text) = &self.after_help {
+            fli_println!("{text}");
+        }
+    }
+
+    /// Renders the help section for a template placeholder like `{usage}`, `None` if it is not one
+    fn render_help_section(&self, placeholder: &str) -> Option<String> {
+        if !HELP_TEMPLATE_PLACEHOLDERS.contains(&placeholder) {
+            return None;
+        }
+        let label = display::theme().label;
+        let messages = messages::messages();
+        let (_, section, _) = display::capture(|| match placeholder {
+            "{name}" => fli_println!(
+                "{0: <1} {1}: {2}",
+                "",
+                messages.name_label.bold().color(label),
+                self.name
+            ),
+            "{version}" => fli_println!(
+                "{0: <1} {1}: {2}",
+                "",
+                messages.version_label.bold().color(label),
+                self.version
+            ),
+            "{description}" => fli_println!(
+                "{0: <1} {1}: {2}",
+                "",
+                messages.description_label.bold().color(label),
+                match &self.long_help {
+                    Some(long_help) if self.show_long_help => long_help,
+                    _ => &self.description,
+                }
+            ),
+            "{usage}" => fli_println!(
+                "{0: <1} {1}: {2}",
+                "",
+                messages.usage_label.bold().color(label),
+                self.usage_line()
+            ),
+            "{arguments}" => self.print_arguments(),
+            "{options}" => self.print_options(),
+            "{subcommands}" => self.print_commands(),
+            _ => {}
+        });
+        Some(section.trim_end_matches('\n').to_string())
+    }
+
+    /// Gets the usage line of the help screen, like `cp [options|commands] <source> <dest>`
+    ///
+    /// # Returns
+    /// * `String` - The usage line set with `set_usage`, or the generated one
+    pub fn get_usage(&self) -> String {
+        self.usage_line()
+    }
+
+    /// Gets the usage line set with `set_usage`, or the generated one with the positional args the command takes
+    /// named by `argument` or from `min_positional_args` and `max_positional_args`
+    fn usage_line(&self) -> String {
+        if let Some(usage) = &self.usage {
+            return usage.to_string();
+        }
+        let mut usage = format!("{} [options|commands]", self.name);
+        if !self.arguments.is_empty() {
+            for (name, _) in &self.arguments {
+                usage = format!("{usage} {name}");
+            }
+            return usage;
+        }
+        let has_bounds = self.min_positional_args > 0 || self.max_positional_args.is_some();
+        if has_bounds {
+            usage += &" <arg>".repeat(self.min_positional_args);
+        }
+        match self.max_positional_args {
+            Some(max) => usage += &" [arg]".repeat(max.saturating_sub(self.min_positional_args)),
+            None if has_bounds => usage += " [arg]...",
+            None => {}
+        }
+        usage
+    }
+
+    pub fn print_most_similar_commands(&self, command: &str) {
+        let index = self.args.iter().skip(1).position(|arg| arg == command);
+        self.print_suggestions(
+            &self.get_most_similar_commands(command),
+            index.map(|index| self.arg_offset + index + 1),
+        );
+    }
+
+    /// Prints the suggestions for a near miss at an index, when there is only one it is printed
+    /// as the command line with the near miss replaced, ready to copy
+    fn print_suggestions(&self, suggestions: &[String], index: Option<usize>) {
+        let fixed = match (suggestions, index) {
+            ([suggestion], Some(index)) => self.fix_it_command_line(index, suggestion),
+            _ => None,
+        };
+        match fixed {
+            Some(fixed) => {
+                let did_you_mean = messages::messages().did_you_mean;
+                fli_println!("{0: <1} {1} {2}", "", did_you_mean.bold().color(display::theme().error), fixed.bold());
+            }
+            None => self.print_did_you_mean(suggestions),
+        }
+    }
+
+    /// Gets the command line with the arg at an index, counted from the app, replaced by a suggestion
+    fn fix_it_command_line(&self, index: usize, suggestion: &str) -> Option<String> {
+        let mut fixed = self.clone();
+        *fixed.args.get_mut(index.checked_sub(self.arg_offset)?)? = suggestion.to_string();
+        Some(fixed.to_command_line())
+    }
+
+    fn print_did_you_mean(&self, suggestions: &[String]) {
+        if !suggestions.is_empty() {
+            let theme = display::theme();
+            let did_you_mean = messages::messages().did_you_mean;
+            fli_println!("{0: <1} {1}", "", did_you_mean.bold().color(theme.error));
+            for i in suggestions {
+                //  give about 2 tap space then a bullet point before showing the similar command
+                fli_println!("{0: <4} {1} {2}", "   ", theme.symbols.bullet.bold().color(theme.error), i.bold());
+            }
+        }
+    }
+
+    /// Gets the registered short and long options close to an unknown option
+    fn get_most_similar_options(&self, option: &str) -> Vec<String> {
+        let mut similar_options: Vec<String> = self
+            .short_hash_table
+            .keys()
+            .cloned()
+            .chain(self.get_long_option_names())
+            .filter(|name| levenshtein_distance(option, name) < 3)
+            .collect();
+        similar_options.sort();
+        similar_options.dedup();
+        similar_options
+    }
+
+    fn get_most_similar_commands(&self, command: &str) -> Vec<String> {
+        //  get commands with distances less than 3
+        let mut similar_commands: Vec<String> = vec![];
+        for key in self.help_keys() {
+            let distance = levenshtein_distance(command, key);
+            if distance < 3 {
+                similar_commands.push(key.to_string());
+            }
+        }
+        similar_commands
+    }
+
+    fn print_arguments(&self) {
+        if self.arguments.is_empty() {
+            return;
+        }
+        let theme = display::theme();
+        let messages = messages::messages();
+        let header = format!("{}:", messages.arguments_header);
+        fli_println!("{0: <1} {1}", "", header.bold().color(theme.header));
+        let mut table = Table::new([
+            &messages.name_column,
+            &messages.param_type_column,
+            &messages.description_column,
+        ]);
+        table.indent(4).color(0, theme.option).color(2, theme.description);
+        for (name, description) in &self.arguments {
+            let (name, template) = split_argument_name(name);
+            table.add_row([name, &param_type_name(&template, &messages), description]);
+        }
+        table.print();
+    }
+
+    fn print_options(&self) {
+        let theme = display::theme();
+        let messages = messages::messages();
+        let new_table = || {
+            let mut table = Table::new([
+                &messages.long_column,
+                &messages.short_column,
+                &messages.param_type_column,
+                &messages.description_column,
+            ]);
+            table
+                .indent(4)
+                .color(0, theme.option)
+                .color(1, theme.short)
+                .color(3, theme.description);
+            table
+        };
+        let mut groups: Vec<(Option<&str>, Table)> = vec![];
+        for key in self.help_keys() {
+            // if a command skip
+            if self.cammands_hash_tables.contains_key(key) {
+                continue;
+            }
+            if let Some(description) = self.help_hash_table.get(key) {
+                let parts: Vec<&str> = key.split(" ").collect();
+                let long = parts.get(1).copied().unwrap_or_default();
+                // options without a short name repeat the long one in their key
+                let short = parts.first().copied().filter(|short| *short != long).unwrap_or_default();
+                let param_type = match parts.get(2).map(|param_d| param_d.trim()) {
+                    Some(template) => param_type_name(template, &messages),
+                    None => String::new(),
+                };
+                let description = self.option_description(long, description);
+                let group = self.option_groups.get(long).map(String::as_str);
+                group_table(&mut groups, group, new_table)
+                    .add_row([long, short, &param_type, &description]);
+            }
+        }
+        self.print_groups(groups, &messages.options_header, new_table);
+    }
+    fn print_commands(&self) {
+        let theme = display::theme();
+        let messages = messages::messages();
+        let new_table = || {
+            let mut table = Table::new([&messages.name_column, &messages.description_column]);
+            table.indent(3).color(0, theme.option).color(1, theme.description);
+            table
+        };
+        let mut groups: Vec<(Option<&str>, Table)> = vec![];
+        for key in self.help_keys() {
+            // if not a command skip
+            let Some(command) = self.cammands_hash_tables.get(key) else {
+                continue;
+            };
+            if let Some(description) = self.help_hash_table.get(key) {
+                let description = self.builtin_description(key, description);
+                group_table(&mut groups, command.category.as_deref(), new_table)
+                    .add_row([key.as_str(), &description]);
+            }
+        }
+        self.print_groups(groups, &messages.commands_header, new_table);
+    }
+
+    /// Gets the description of an option in the help screen, with its long description and choices in the long one
+    fn option_description(&self, long: &str, description: &str) -> String {
+        if !self.show_long_help {
+            return self.builtin_description(long, description);
+        }
+        let mut description = match self.option_long_helps.get(long) {
+            Some(long_help) => long_help.to_string(),
+            None => self.builtin_description(long, description),
+        };
+        let choices = self.value_hints.get(long).map(ValueHint::values).unwrap_or_default();
+        if !choices.is_empty() {
+            let values = fill(&messages::messages().possible_values, &[("values", &choices.join(", "))]);
+            description = format!("{description} {values}");
+        }
+        description
+    }
+
+    /// Gets the description of an option or command in the language of the messages
+    /// if it is a built-in one still described by its English default
+    fn builtin_description(&self, long: &str, description: &str) -> String {
+        let name = [("name", self.name.as_str())];
+        let path = [("path", self.config_file.as_deref().unwrap_or_default())];
+        let english = Messages::english();
+        let messages = messages::messages();
+        let (default, localized) = match long {
+            "--help" => (fill(&english.help_option, &name), fill(&messages.help_option, &name)),
+            "--version" => (fill(&english.version_option, &name), fill(&messages.version_option, &name)),
+            "help" => (fill(&english.help_command, &name), fill(&messages.help_command, &name)),
+            "--config" => (fill(&english.config_option, &path), fill(&messages.config_option, &path)),
+            "--color" => (english.color_option, messages.color_option),
+            "--verbose" => (english.verbose_option, messages.verbose_option),
+            "--quiet" => (english.quiet_option, messages.quiet_option),
+            _ => return description.to_string(),
+        };
+        if description == default {
+            localized
+        } else {
+            description.to_string()
+        }
+    }
+
+    /// Prints tables under their group header
+    /// the one without a group first under `default_header`, then the groups in the help order
+    fn print_groups(
+        &self,
+        mut groups: Vec<(Option<&str>, Table)>,
+        default_header: &str,
+        new_table: impl Fn() -> Table,
+    ) {
+        let theme = display::theme();
+        if groups.is_empty() {
+            groups.push((None, new_table()));
+        }
+        match self.help_sort {
+            Some(HelpOrder::Alphabetical) => groups.sort_by_key(|(group, _)| *group),
+            _ => groups.sort_by_key(|(group, _)| group.is_some()),
+        }
+        for (group, table) in groups {
+            let header = format!("{}:", group.unwrap_or(default_header));
+            fli_println!("{0: <1} {1}", "", header.bold().color(theme.header));
+            table.print();
+        }
+    }
+    pub fn default(&mut self, callback: fn(app: &Self)) -> &mut Self {
+        self.default_callback = callback;
+        self
+    }
+
+    /// Sets the function `run` hands errors to, instead of printing them with the help screen
+    /// the app exits with the code it returns, commands without their own handler use their parent's
+    /// # Arguments
+    /// * `handler` - The function to handle the error, returning the exit code
+    ///
+    /// # Example
+    /// ```
+    /// app.on_error(|error| {
+    ///     fli_eprintln!("myapp: {error}");
+    ///     2
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn on_error(&mut self, handler: fn(error: &FliError) -> i32) -> &mut Self {
+        self.error_handler = Some(handler);
+        self
+    }
+
+    /// Sets the code `run` exits with for a kind of error, `FliErrorKind::default_exit_code` (2) when not set
+    /// or the sysexits code after `use_sysexits`
+    /// commands use the codes of their parent for kinds they do not set
+    /// # Arguments
+    /// * `kind` - The kind of error
+    /// * `code` - The exit code
+    ///
+    /// # Example
+    /// ```
+    /// app.set_exit_code(FliErrorKind::UnknownOption, 64); // EX_USAGE
+    /// app.set_exit_code(FliErrorKind::InvalidValue, 65); // EX_DATAERR
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn set_exit_code(&mut self, kind: FliErrorKind, code: i32) -> &mut Self {
+        self.exit_codes.insert(kind, code);
+        self
+    }
+
+    /// Sets the exit codes of the kinds of error not set with `set_exit_code` from the BSD sysexits table,
+    /// see `FliErrorKind::sysexits_code`
+    ///
+    /// # Example
+    /// ```
+    /// app.use_sysexits();
+    /// // $ app --unknown   => exits with 64 (EX_USAGE)
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn use_sysexits(&mut self) -> &mut Self {
+        for kind in FliErrorKind::ALL {
+            self.exit_codes.entry(kind).or_insert(kind.sysexits_code());
+        }
+        self
+    }
+
+    /// Gets the code `run` exits with for an error, running the error handler set with `on_error` if there is one
+    pub(crate) fn error_exit_code(&self, error: &FliError) -> i32 {
+        match self.error_handler {
+            Some(handler) => handler(error),
+            None => self.get_exit_code(error),
+        }
+    }
+
+    /// Gets the code `run` exits with for an error
+    pub fn get_exit_code(&self, error: &FliError) -> i32 {
+        let kind = error.kind();
+        self.exit_codes
+            .get(&kind)
+            .copied()
+            .unwrap_or_else(|| kind.default_exit_code())
+    }
+
+    /// Adds a hook to run before the callbacks of this command
+    /// hooks only run once the args are parsed without errors, in the order they were added
+    /// # Arguments
+    /// * `hook` - The function to run
+    ///
+    /// # Example
+    /// ```
+    /// app.command("deploy", "Deploy the app")
+    ///     .before(|x| check_credentials())
+    ///     .after(|x| fli_println!("done"));
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn before(&mut self, hook: fn(app: &Self)) -> &mut Self {
+        self.before_hooks.push(hook);
+        self
+    }
+
+    /// Adds a hook to run after the callbacks of this command
+    /// it also runs when a hook or callback stopped them with `request_exit`, like `--help` and `--version` do
+    /// # Arguments
+    /// * `hook` - The function to run
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn after(&mut self, hook: fn(app: &Self)) -> &mut Self {
+        self.after_hooks.push(hook);
+        self
+    }
+
+    /// Adds a function checking the args once they are parsed, before the hooks and callbacks of this command run,
+    /// for rules spanning more than one option like "either --name or --id, not both"
+    /// an `Err` is reported like any other error, as a `ValidationFailed` error
+    /// # Arguments
+    /// * `validator` - The function checking the args, returning why they are not valid
+    ///
+    /// # Example
+    /// ```
+    /// app.command("show", "Show a user")
+    ///     .option("-n --name, <>", "The name of the user", |x| {})
+    ///     .option("-i --id, <>", "The id of the user", |x| {})
+    ///     .validate(|x| {
+    ///         if x.is_passed("--name".to_string()) == x.is_passed("--id".to_string()) {
+    ///             return Err("pass either --name or --id".to_string());
+    ///         }
+    ///         Ok(())
+    ///     });
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn validate(&mut self, validator: Validator) -> &mut Self {
+        self.validators.push(validator);
+        self
+    }
+
+    /// Adds a hook to run before the callbacks of this command and every command under it
+    /// they run before the `before` hooks of the command, useful for auth checks or timing
+    /// # Arguments
+    /// * `hook` - The function to run, it gets the command being run
+    ///
+    /// # Example
+    /// ```
+    /// app.before_each(|x| fli_println!("running {}", x.get_app_name()));
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn before_each(&mut self, hook: fn(app: &Self)) -> &mut Self {
+        self.before_each_hooks.push(hook);
+        self
+    }
+
+    /// Adds a hook to run after the callbacks of this command and every command under it
+    /// they run after the `after` hooks of the command, also when the callbacks were stopped with `request_exit`, useful for cleanup
+    /// # Arguments
+    /// * `hook` - The function to run, it gets the command being run
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn after_each(&mut self, hook: fn(app: &Self)) -> &mut Self {
+        self.after_each_hooks.push(hook);
+        self
+    }
+
+    /// Sets a callback to run instead of the "Command not found" error when a command that does not exist is passed
+    /// it gets the raw args (without the runner), useful to forward unknown commands to another program
+    /// # Arguments
+    /// * `callback` - The callback to run with the app and the raw args
+    ///
+    /// # Example
+    /// ```
+    /// app.on_unmatched(|x, args| {
+    ///     // $ git-wrapper stash list => forwards ["stash", "list"] to git
+    ///     std::process::Command::new("git").args(args).status();
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn on_unmatched(&mut self, callback: fn(app: &Self, args: &[String])) -> &mut Self {
+        self.unmatched_callback = Some(callback);
+        self
+    }
+
+    pub fn option(&mut self, key: &str, description: &str, value: fn(app: &Self)) -> &mut Self {
+        let args: Vec<&str> = key.split(",").collect();
+        let mut options = String::new();
+        if let Some(opts) = args.first() {
+            options = String::from(opts.to_owned());
+        }
+        let broken_args: Vec<_> = options.split(" ").collect();
+        let short = broken_args[0].trim();
+        let mut long = broken_args[0].trim();
+        if broken_args.len() > 1 {
+            long = broken_args[1].trim();
+        }
+        if long == "--color" && self.builtin_color {
+            // the app's own `--color` replaces the built-in one
+            self.remove_option(long);
+        }
+        if broken_args.len() > 1 {
+            self.short_hash_table
+                .insert(short.to_string(), long.to_string());
+        }
+        // a hidden alias of another option gives way to the option using its name
+        self.option_names.remove(short);
+        self.option_names.remove(long);
+        if let Some(index) = self.inherited_options.iter().position(|name| name == long) {
+            // the command's own option replaces the inherited one
+            self.inherited_options.remove(index);
+            self.inheritable_options.retain(|name| name != long);
+            self.remove_option(long);
+        }
+        // for i in options.split(" ") {
+        let mut param_type = String::new();
+        if let Some(param_d) = args.get(1) {
+            param_type = String::from(param_d.to_owned());
+        }
+        if args.len() > 1 && !["<>", "[]", "<...>", "[...]"].contains(&param_type.trim()) {
             self.print_help(&format!("Error : unknown param type {param_type}"));
         }
-        let option: String = long.trim().to_owned() + " " + param_type.trim();
-        self.args_hash_table.insert(option.trim().to_owned(), value);
-        self.help_hash_table.insert(
-            short.to_string() + " " + option.trim(),
-            description.to_string(),
-        );
-        // }
-        return self;
+        let option: String = long.trim().to_owned() + " " + param_type.trim();
+        self.args_hash_table.insert(option.trim().to_owned(), value);
+        self.insert_help(short.to_string() + " " + option.trim(), description.to_string());
+        // }
+        if self.all_inheritable {
+            self.mark_inheritable(long);
+        }
+        self
+    }
+    /// Copies an option with its callback to all the commands of the app or command, including the ones added later
+    /// so `app start --debug` runs the callback of `--debug`, called with `start`. Commands with an option of the same name keep theirs
+    /// # Arguments
+    /// * `option` - The option to copy (`-d`, `--debug`, `d` or `debug`), it must already be added
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-d --debug", "Print debug output", |x| {});
+    /// app.mark_inheritable("--debug");
+    /// app.command("start", "Start the server");
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn mark_inheritable(&mut self, option: &str) -> &mut Self {
+        let long = self.get_callable_name(option.to_string());
+        if self.get_param_type(&long).is_none() || self.inheritable_options.contains(&long) {
+            return self;
+        }
+        if self.all_inheritable && ["--help", "--version"].contains(&long.as_str()) {
+            return self;
+        }
+        let app = self.clone();
+        for command in self.cammands_hash_tables.values_mut() {
+            command.inherit_option(&app, &long);
+        }
+        self.inheritable_options.push(long);
+        self
+    }
+
+    /// Marks all the options of the app or command as inheritable, including the ones added later, see `mark_inheritable`
+    /// `--help` and `--version` are left out as every command has its own help and the version is the app's
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn mark_all_inheritable(&mut self) -> &mut Self {
+        self.all_inheritable = true;
+        for long in self.get_long_option_names() {
+            self.mark_inheritable(&long);
+        }
+        self
+    }
+
+    /// Adds an option and marks it inheritable, so all the commands get it, see `mark_inheritable`
+    /// # Arguments
+    /// * `key` - The option like in `option`, `-d --debug`
+    /// * `description` - The description of the option
+    /// * `value` - The callback of the option
+    ///
+    /// # Example
+    /// ```
+    /// app.add_global_option("-d --debug", "Print debug output", |x| {});
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn add_global_option(&mut self, key: &str, description: &str, value: fn(app: &Self)) -> &mut Self {
+        self.option(key, description, value);
+        let options = key.split(',').next().unwrap_or_default();
+        if let Some(long) = options.split_whitespace().last() {
+            self.mark_inheritable(long);
+        }
+        self
+    }
+
+    /// Copies an inheritable option of the parent to this command and its commands
+    fn inherit_option(&mut self, parent: &Fli, long: &str) {
+        if self.get_param_type(long).is_some() {
+            return;
+        }
+        let Some(param_type) = parent.get_param_type(long) else {
+            return;
+        };
+        let Some(callback) = parent.args_hash_table.get(format!("{long} {param_type}").trim()).copied() else {
+            return;
+        };
+        let short = parent
+            .short_hash_table
+            .iter()
+            .find(|(_, name)| *name == long)
+            .map(|(short, _)| short.as_str());
+        let names = match short {
+            Some(short) if !self.short_hash_table.contains_key(short) => format!("{short} {long}"),
+            _ => long.to_string(),
+        };
+        let key = match param_type {
+            "" => names,
+            param_type => format!("{names}, {param_type}"),
+        };
+        let description = parent
+            .help_keys()
+            .into_iter()
+            .find(|key| key.split_whitespace().nth(1) == Some(long))
+            .and_then(|key| parent.help_hash_table.get(key))
+            .cloned()
+            .unwrap_or_default();
+        self.option(&key, &description, callback);
+        self.inherited_options.push(long.to_string());
+        self.inheritable_options.push(long.to_string());
+        let app = self.clone();
+        for command in self.cammands_hash_tables.values_mut() {
+            command.inherit_option(&app, long);
+        }
+    }
+
+    /// Removes an option from the app or command, like the `--color` option every command gets
+    /// # Arguments
+    /// * `option` - The option to remove (`-c`, `--color`, `c` or `color`)
+    ///
+    /// # Example
+    /// ```
+    /// app.command("serve", "Serve the app").remove_option("--color");
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn remove_option(&mut self, option: &str) -> &mut Self {
+        let long = self.get_callable_name(option.to_string());
+        if long == "--color" {
+            self.builtin_color = false;
+        }
+        for template in ["", "[]", "<>", "[...]", "<...>"] {
+            self.args_hash_table.remove(format!("{long} {template}").trim());
+        }
+        self.short_hash_table.retain(|_, name| *name != long);
+        let help_keys: Vec<String> = self
+            .help_order
+            .iter()
+            .filter(|key| key.split_whitespace().nth(1) == Some(long.as_str()))
+            .cloned()
+            .collect();
+        for key in help_keys {
+            self.remove_help(&key);
+        }
+        self.deprecated_hash_table.remove(&long);
+        self.option_groups.remove(&long);
+        self.option_priorities.remove(&long);
+        self.dynamic_defaults.remove(&long);
+        self.default_values.remove(&long);
+        self.value_checks.remove(&long);
+        self.arg_actions.remove(&long);
+        self.value_hints.remove(&long);
+        self.option_long_helps.remove(&long);
+        self.value_counts.remove(&long);
+        self.exclusive_options.retain(|name| *name != long);
+        self.option_names.retain(|_, name| *name != long);
+        self
+    }
+
+    /// Replaces an option of the app or command with another of the same long name,
+    /// instead of keeping both when they differ in short name or value type
+    /// # Arguments
+    /// * `key` - The option like in `option`, `-c --count, <>`
+    /// * `description` - The description of the option
+    /// * `value` - The callback of the option
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-c --config, <>", "The config file", |x| {});
+    /// // `check --config` is a flag instead
+    /// app.command("check", "Check the config").override_option("--config", "Check the config file too", |x| {});
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn override_option(&mut self, key: &str, description: &str, value: fn(app: &Self)) -> &mut Self {
+        let options = key.split(',').next().unwrap_or_default();
+        if let Some(long) = options.split_whitespace().last() {
+            self.remove_option(long);
+        }
+        self.option(key, description, value)
+    }
+
+    /// Gives an option a default value computed when it is read and was not passed or set in the config file,
+    /// so it can depend on the environment, the terminal or the other options
+    /// # Arguments
+    /// * `option` - The option (`-j`, `--jobs`, `j` or `jobs`), it must take a value
+    /// * `default` - The function computing the value
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-j --jobs, <>", "The number of jobs to run at once", |x| {});
+    /// app.default_with("--jobs", |_app| {
+    ///     std::thread::available_parallelism().map_or(1, |jobs| jobs.get()).to_string()
+    /// });
+    /// app.default(|x| {
+    ///     let jobs = x.get_values("jobs".to_string()).unwrap();
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn default_with(&mut self, option: &str, default: fn(app: &Self) -> String) -> &mut Self {
+        let long = self.get_callable_name(option.to_string());
+        self.dynamic_defaults.insert(long, default);
+        self
+    }
+
+    /// Gives an option a default value used when it is not passed or set in the config file
+    /// # Arguments
+    /// * `option` - The option (`-p`, `--port`, `p` or `port`), it must take a value
+    /// * `value` - The default value
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-p --port, <>", "The port to listen on", |x| {});
+    /// app.default_value("--port", "8080");
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn default_value(&mut self, option: &str, value: &str) -> &mut Self {
+        let long = self.get_callable_name(option.to_string());
+        self.default_values.insert(long, value.to_string());
+        self
+    }
+
+    /// Sets how many values an option taking many values (`[...]` or `<...>`) accepts when it is passed,
+    /// passing fewer or more is an `InvalidValue` error naming the bound
+    /// # Arguments
+    /// * `option` - The option (`-p`, `--point`, `p` or `point`)
+    /// * `min` - The fewest values
+    /// * `max` - The most values, none for no limit. `Some(min)` asks for exactly `min` values
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-p --point, <...>", "A point as x y [z]", |x| {});
+    /// app.value_count("--point", 2, Some(3));
+    /// // $ app --point 4   => Invalid value for --point : expected at least 2 values, got 1
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn value_count(&mut self, option: &str, min: usize, max: Option<usize>) -> &mut Self {
+        let long = self.get_callable_name(option.to_string());
+        self.value_counts.insert(long, (min, max));
+        self
+    }
+
+    /// Sets a token ending the values of options taking many values (`[...]` or `<...>`), which otherwise take
+    /// every value up to the next option. The token itself is dropped, the args after it are read as usual.
+    /// Commands without their own use their parent's
+    /// # Arguments
+    /// * `terminator` - The token, `;` like `find -exec` (quote it in shells)
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-f --files, <...>", "The files to copy", |x| {});
+    /// app.value_terminator(";");
+    /// // $ app -f a.txt b.txt ';' backup/   => files ["a.txt", "b.txt"] and the positional arg "backup/"
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn value_terminator(&mut self, terminator: &str) -> &mut Self {
+        self.value_terminator = Some(terminator.to_string());
+        self
+    }
+
+    /// Makes an option unusable with any other option or positional arg, passing it with one is an `ExclusiveOption` error.
+    /// For single purpose options like `--version` or `--completions`
+    /// # Arguments
+    /// * `option` - The option (`-V`, `--version`, `V` or `version`)
+    ///
+    /// # Example
+    /// ```
+    /// app.option("--completions, <>", "Print the completion script for a shell", |x| {});
+    /// app.exclusive("--completions");
+    /// // $ app --completions bash --verbose   => --completions can not be used with --verbose
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn exclusive(&mut self, option: &str) -> &mut Self {
+        let long = self.get_callable_name(option.to_string());
+        if !self.exclusive_options.contains(&long) {
+            self.exclusive_options.push(long);
+        }
+        self
+    }
+
+    /// Sets how an option behaves when it is passed
+    /// `Help` and `Version` replace the callback of the option and take the priority of `--help` and `--version`
+    /// # Arguments
+    /// * `option` - The option (`-I`, `--include`, `I` or `include`), it must already be added
+    /// * `action` - The action, see `ArgAction`
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-I --include, <>", "Add a directory to the search path", |x| {});
+    /// app.set_arg_action("--include", ArgAction::Append);
+    /// app.option("--no-color", "Do not color output", |x| {});
+    /// app.set_arg_action("--no-color", ArgAction::SetFalse);
+    /// app.default(|x| {
+    ///     // $ app -I src -I lib   => ["src", "lib"]
+    ///     let includes = x.get_values("include".to_string()).unwrap_or_default();
+    ///     let color = x.get_flag("--no-color");
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn set_arg_action(&mut self, option: &str, action: ArgAction) -> &mut Self {
+        let long = self.get_callable_name(option.to_string());
+        let (callback, priority): (fn(&Fli), i32) = match action {
+            ArgAction::Help => (help_callback, HELP_PRIORITY),
+            ArgAction::Version => (version_callback, VERSION_PRIORITY),
+            _ => {
+                self.arg_actions.insert(long, action);
+                return self;
+            }
+        };
+        if let Some(template) = self.get_param_type(&long) {
+            self.args_hash_table
+                .insert(format!("{long} {template}").trim().to_string(), callback);
+            self.option_priorities.insert(long.to_string(), priority);
+        }
+        self.arg_actions.insert(long, action);
+        self
+    }
+
+    /// Sets what the value of an option is, the completion specs complete it
+    /// and `ask_value` offers the choices when it is not passed
+    /// # Arguments
+    /// * `option` - The option (`-o`, `--output`, `o` or `output`), it must already be added
+    /// * `hint` - What the value is, see `ValueHint`
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-o --output, <>", "Where to write", |x| {});
+    /// app.value_hint("--output", ValueHint::FilePath);
+    /// app.option("-f --format, <>", "The output format", |x| {});
+    /// app.value_hint("--format", ValueHint::Choices(vec!["json".to_string(), "yaml".to_string()]));
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn value_hint(&mut self, option: &str, hint: ValueHint) -> &mut Self {
+        let long = self.get_callable_name(option.to_string());
+        self.value_hints.insert(long, hint);
+        self
+    }
+
+    /// Gets what the value of an option is, set with `value_hint`
+    /// # Arguments
+    /// * `option` - The option (`-o`, `--output`, `o` or `output`)
+    ///
+    /// # Returns
+    /// * `Option<&ValueHint>` - The hint, none if it has none
+    pub fn get_value_hint(&self, option: &str) -> Option<&ValueHint> {
+        self.value_hints.get(&self.get_callable_name(option.to_string()))
+    }
+
+    /// Gets the value of an option, asking for it when it is not passed
+    /// one of the choices of its `value_hint` is picked with `prompt::select`, other values are typed with `prompt::input`
+    /// # Arguments
+    /// * `option` - The option (`-f`, `--format`, `f` or `format`)
+    ///
+    /// # Example
+    /// ```
+    /// app.default(|x| {
+    ///     // $ app   => format? 1) json 2) yaml
+    ///     let format = x.ask_value("--format").unwrap();
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `Result<String, String>` - The value, an error if there is nothing to read or input is turned off
+    pub fn ask_value(&self, option: &str) -> Result<String, String> {
+        let long = self.get_callable_name(option.to_string());
+        if let Some(value) = self.get_values(long.to_string()).ok().and_then(|values| values.into_iter().next()) {
+            return Ok(value);
+        }
+        let question = self
+            .help_keys()
+            .into_iter()
+            .find(|key| key.split_whitespace().nth(1) == Some(long.as_str()))
+            .and_then(|key| self.help_hash_table.get(key))
+            .cloned()
+            .unwrap_or(long);
+        let choices = self.get_value_hint(option).map(ValueHint::values).unwrap_or_default();
+        if choices.is_empty() {
+            return prompt::input(&question);
+        }
+        prompt::select(&question, &choices).map(|index| choices[index].to_string())
+    }
+
+    /// Reads a flag set with the `SetTrue` or `SetFalse` action, other options are true when passed
+    /// # Arguments
+    /// * `option` - The option (`-f`, `--force`, `f` or `force`)
+    ///
+    /// # Returns
+    /// * `bool` - Whether the flag is on
+    pub fn get_flag(&self, option: &str) -> bool {
+        let long = self.get_callable_name(option.to_string());
+        let passed = self.is_passed(long.to_string());
+        match self.arg_actions.get(&long) {
+            Some(ArgAction::SetFalse) => !passed,
+            _ => passed,
+        }
+    }
+
+    /// Gets how many times an option was passed, for options with the `Count` action like `-vvv`
+    /// # Arguments
+    /// * `option` - The option (`-v`, `--verbose`, `v` or `verbose`)
+    ///
+    /// # Returns
+    /// * `usize` - The number of times it was passed, 0 when it is not an option of the command
+    pub fn get_count(&self, option: &str) -> usize {
+        self.occurrences(option).len()
+    }
+
+    /// Gets the positions in the command line where an option was passed, the runner being 0
+    /// for order sensitive options, like include paths searched in the order they were given
+    /// # Arguments
+    /// * `option` - The option (`-I`, `--include`, `I` or `include`)
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-I --include, <>", "Add a directory to the search path", |x| {});
+    /// app.option("-L --library, <>", "Add a library directory", |x| {});
+    /// app.default(|x| {
+    ///     // $ app -I src -L lib -I vendor   => [1, 5] and [3]
+    ///     let includes = x.occurrences("include");
+    ///     let libraries = x.occurrences("library");
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `Vec<usize>` - The positions in order, empty when it was not passed or is not an option of the command
+    pub fn occurrences(&self, option: &str) -> Vec<usize> {
+        let long = self.get_callable_name(option.to_string());
+        if self.get_param_type(&long).is_none() {
+            return vec![];
+        }
+        self.parsed_args()
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, arg)| self.is_option_token(arg) && self.get_callable_name(arg.to_string()) == long)
+            .map(|(index, _)| self.arg_offset + index)
+            .collect()
+    }
+
+    /// Adds an option without a value, read with `is_passed`
+    /// # Arguments
+    /// * `short` - The short name like `-v`, empty for none
+    /// * `long` - The long name like `--verbose`
+    /// * `description` - The description of the option
+    ///
+    /// # Example
+    /// ```
+    /// app.add_flag("-f", "--force", "Overwrite existing files");
+    /// app.default(|x| {
+    ///     if x.is_passed("--force".to_string()) {}
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn add_flag(&mut self, short: &str, long: &str, description: &str) -> &mut Self {
+        self.option(&option_key(short, long, ""), description, |_x| {})
+    }
+
+    /// Adds an option taking a text value, read with `get_value::<String>`
+    /// # Arguments
+    /// * `short` - The short name like `-n`, empty for none
+    /// * `long` - The long name like `--name`
+    /// * `description` - The description of the option
+    /// * `default` - The value when the option is not passed, none to make it optional
+    ///
+    /// # Example
+    /// ```
+    /// app.add_string_option("-n", "--name", "Your name", Some("world"));
+    /// app.default(|x| {
+    ///     let name: String = x.get_value("name".to_string()).unwrap();
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn add_string_option(
+        &mut self,
+        short: &str,
+        long: &str,
+        description: &str,
+        default: Option<&str>,
+    ) -> &mut Self {
+        self.add_typed_option::<String>(short, long, description, default.map(str::to_string))
+    }
+
+    /// Adds an option taking a whole number, an error is shown when the value passed is not one, read with `get_value::<i64>`
+    /// # Arguments
+    /// * `short` - The short name like `-p`, empty for none
+    /// * `long` - The long name like `--port`
+    /// * `description` - The description of the option
+    /// * `default` - The value when the option is not passed, none to make it optional
+    ///
+    /// # Example
+    /// ```
+    /// app.add_int_option("-p", "--port", "The port to listen on", Some(8080));
+    /// app.default(|x| {
+    ///     let port: u16 = x.get_value("port".to_string()).unwrap();
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn add_int_option(
+        &mut self,
+        short: &str,
+        long: &str,
+        description: &str,
+        default: Option<i64>,
+    ) -> &mut Self {
+        self.add_typed_option::<i64>(short, long, description, default.map(|value| value.to_string()))
+    }
+
+    /// Adds an option taking a number, an error is shown when the value passed is not one, read with `get_value::<f64>`
+    /// # Arguments
+    /// * `short` - The short name like `-r`, empty for none
+    /// * `long` - The long name like `--ratio`
+    /// * `description` - The description of the option
+    /// * `default` - The value when the option is not passed, none to make it optional
+    ///
+    /// # Returns
+    /// * `&mut Fli` - The Fli struct
+    pub fn add_float_option(
+        &mut self,
+        short: &str,
+        long: &str,
+        description: &str,
+        default: Option<f64>,
+    ) -> &mut Self {
+        self.add_typed_option::<f64>(short, long, description, default.map(|value| value.to_string()))
+    }
+
+    /// Adds an option taking one value that must parse as `T`, with its default value
+    fn add_typed_option<T: FliValue>(
+        &mut self,
+        short: &str,
+        long: &str,
+        description: &str,
+        default: Option<String>,
+    ) -> &mut Self {
+        self.option(&option_key(short, long, "<>"), description, |_x| {});
+        let long = self.get_callable_name(long.to_string());
+        self.value_checks.insert(long.to_string(), |value| T::parse(value).map(|_| ()));
+        let choices = T::completion_hints();
+        if !choices.is_empty() {
+            self.value_hints.insert(long.to_string(), ValueHint::Choices(choices));
+        }
+        if let Some(default) = default {
+            self.default_values.insert(long, default);
+        }
+        self
+    }
+
+    pub fn get_params_callback(&mut self, key: String) -> Option<&for<'a> fn(&'a Fli)> {
+        if let Some(callback) = self.args_hash_table.get(&self.get_callable_name(key)) {
+            return Some(callback);
+        }
+        None
+    }
+    pub fn run(&self) -> &Fli {
+        if let Ok(Action::Exit(code)) = self.execute(|app, error| app.report_error(error)) {
+            exit(code);
+        }
+        self
+    }
+
+    /// Runs the app on the given args instead of `env::args()`
+    /// useful for tests, REPLs or apps embedding another app
+    /// # Arguments
+    /// * `args` - The args to parse, the first one being the app runner like in `env::args()`
+    ///
+    /// # Example
+    /// ```
+    /// let mut app : Fli = Fli::init("name", "a sample app");
+    /// app.run_from(["name", "greet", "-n", "james"].map(String::from));
+    /// ```
+    ///
+    /// # Returns
+    /// * `&Fli` - The Fli struct
+    pub fn run_from<I: IntoIterator<Item = String>>(&mut self, args: I) -> &Fli {
+        self.set_args(args.into_iter().collect());
+        self.run()
+    }
+
+    /// Runs the app without exiting on errors, returning them instead
+    ///
+    /// # Example
+    /// ```
+    /// let mut app : Fli = Fli::init("name", "a sample app");
+    /// if let Err(error) = app.try_run() {
+    ///    eprintln!("{error}");
+    /// }
+    /// ```
+    ///
+    /// # Returns
+    /// * `Result<(), FliError>` - An error if the passed args are not valid for the app
+    pub fn try_run(&self) -> Result<(), FliError> {
+        self.try_run_action().map(|_| ())
+    }
+
+    /// Runs the app without exiting, returning errors and whether a callback asked to exit
+    /// (like the help and version options) instead
+    ///
+    /// # Example
+    /// ```
+    /// if let Ok(Action::Exit(code)) = app.try_run_action() {
+    ///     // the help screen or version was printed
+    /// }
+    /// ```
+    ///
+    /// # Returns
+    /// * `Result<Action, FliError>` - The action the callbacks ended with, or the error
+    pub fn try_run_action(&self) -> Result<Action, FliError> {
+        self.execute(|_app, _error| {})
+    }
+
+    /// Runs the app without exiting like `try_run_action`, handing errors to `report` with the command they happened in
+    pub(crate) fn try_run_reporting(&self, report: fn(&Fli, &FliError)) -> Result<Action, FliError> {
+        self.execute(report)
+    }
+
+    /// Runs the app on the given args instead of `env::args()`, returning errors instead of exiting
+    /// # Arguments
+    /// * `args` - The args to parse, the first one being the app runner like in `env::args()`
+    ///
+    /// # Example
+    /// ```
+    /// let mut app : Fli = Fli::init("name", "a sample app");
+    /// app.try_run_from(["name", "greet", "-n", "james"].map(String::from))?;
+    /// ```
+    ///
+    /// # Returns
+    /// * `Result<(), FliError>` - An error if the passed args are not valid for the app
+    pub fn try_run_from<I: IntoIterator<Item = String>>(
+        &mut self,
+        args: I,
+    ) -> Result<(), FliError> {
+        self.set_args(args.into_iter().collect());
+        self.try_run()
+    }
+
+    /// Runs the app as an interactive shell, each line typed is split like a shell would and run like the args of the app
+    /// errors, exits and the help screen end the line instead of the shell,
+    /// `history` lists the lines run so far, `!!` runs the last one again and `!3` the third,
+    /// `exit`, `quit` or the end of the input leave the shell.
+    /// With the `readline` feature the arrow keys go through the lines run and tab completes commands and options, see `complete`
+    ///
+    /// # Example
+    /// ```
+    /// let mut app : Fli = Fli::init("admin", "an admin tool");
+    /// app.command("users", "Manage users").option("-a --add, <>", "Add a user", add_user);
+    /// if app.get_arg_at(1).is_none() {
+    ///     app.repl(); // admin> users --add "Jane Doe"
+    /// } else {
+    ///     app.run();
+    /// }
+    /// ```
+    pub fn repl(&self) {
+        let _messages = messages::scoped_messages(self.messages.clone());
+        let mut history: Vec<String> = vec![];
+        #[cfg(feature = "readline")]
+        let mut editor = crate::readline::LineEditor::new(self);
+        loop {
+            let prompt = format!("{}> ", self.name);
+            #[cfg(feature = "readline")]
+            let line = match editor.as_mut() {
+                Some(editor) => editor.read_line(&prompt),
+                None => {
+                    display::print(format_args!("{prompt}"));
+                    prompt::read_line()
+                }
+            };
+            #[cfg(not(feature = "readline"))]
+            let line = {
+                display::print(format_args!("{prompt}"));
+                prompt::read_line()
+            };
+            let Some(mut line) = line else {
+                fli_println!();
+                break;
+            };
+            match recall_history_line(&history, &line) {
+                Some(Ok(recalled)) => {
+                    fli_println!("{recalled}");
+                    line = recalled;
+                }
+                Some(Err(message)) => {
+                    fli_eprintln!("{message}");
+                    continue;
+                }
+                None => {}
+            }
+            match line.as_str() {
+                "" => continue,
+                "exit" | "quit" => break,
+                "history" => {
+                    for (number, line) in history.iter().enumerate() {
+                        fli_println!("{: >4}  {}", number + 1, line);
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+            history.push(line.to_string());
+            #[cfg(feature = "readline")]
+            if let Some(editor) = editor.as_mut() {
+                editor.add_history(&line);
+            }
+            let words = match split_shell_words(&line) {
+                Ok(words) => words,
+                Err(message) => {
+                    fli_eprintln!("{message}");
+                    continue;
+                }
+            };
+            let mut app = self.clone();
+            app.set_args([self.name.to_string()].into_iter().chain(words).collect());
+            // errors exit after being shown, help and version just end the line
+            let _ = catch_exit(|| app.execute(|app, error| app.report_error(error)));
+        }
+    }
+
+    /// Gets the commands or options that can complete the last word of a line, from the commands and options of the app
+    /// the last word is completed with options when it starts with `-` and commands otherwise
+    /// # Arguments
+    /// * `line` - The line typed so far, without the app name
+    ///
+    /// # Example
+    /// ```
+    /// app.command("remote", "Manage remotes").option("-v --verbose", "Print more", |x| {});
+    /// app.complete("rem"); // ["remote"]
+    /// app.complete("remote --ve"); // ["--verbose"]
+    /// ```
+    ///
+    /// # Returns
+    /// * `Vec<String>` - The sorted candidates
+    pub fn complete(&self, line: &str) -> Vec<String> {
+        let mut words = split_shell_words(line)
+            .unwrap_or_else(|_| line.split_whitespace().map(String::from).collect());
+        let current = match line.ends_with(char::is_whitespace) {
+            true => String::new(),
+            false => words.pop().unwrap_or_default(),
+        };
+        let mut command = self;
+        for word in &words {
+            if let Some(subcommand) = command.find_command(word) {
+                command = subcommand;
+            }
+        }
+        let mut candidates: Vec<String> = if current.starts_with('-') {
+            let mut options = command.get_long_option_names();
+            options.extend(command.short_hash_table.keys().cloned());
+            options
+        } else {
+            command.cammands_hash_tables.keys().cloned().collect()
+        };
+        candidates.retain(|candidate| candidate.starts_with(&current));
+        candidates.sort();
+        candidates
+    }
+
+    /// Parses the args and runs the callbacks, calling `report` on the command the error happened in
+    /// the error is returned in a `FliError::InCommand` naming that command when it is not the app
+    fn execute(&self, report: fn(&Fli, &FliError)) -> Result<Action, FliError> {
+        self.execute_here(report).map_err(|error| match error {
+            FliError::InCommand { .. } => error,
+            error if self.parent.is_some() => FliError::InCommand {
+                command_path: self.command_path().iter().map(|name| name.to_string()).collect(),
+                error: Box::new(error),
+            },
+            error => error,
+        })
+    }
+
+    /// Parses the args and runs the callbacks of this command, see `execute`
+    fn execute_here(&self, report: fn(&Fli, &FliError)) -> Result<Action, FliError> {
+        let mut callbacks = vec![];
+        let mut errors = self.parent_errors.clone();
+        self.action.set(Action::Continue);
+        if let Some(theme) = &self.theme {
+            display::set_theme(theme.clone());
+        }
+        if let Some(messages) = &self.messages {
+            messages::set_messages(messages.clone());
+        }
+        if let Some(ascii_output) = self.ascii_output {
+            display::set_ascii_output(ascii_output);
+        }
+        match self.expand_response_files() {
+            Some(Ok(app)) => return app.execute(report),
+            Some(Err(error)) => {
+                report(self, &error);
+                return Err(error);
+            }
+            None => {}
+        }
+        if let Some(app) = self.expand_option_forms() {
+            return app.execute(report);
+        }
+        #[cfg(feature = "dotenv")]
+        self.apply_dotenv();
+        if let Err(error) = self.load_config() {
+            self.report_or_collect(error, &mut errors, report)?;
+        }
+        if let Err(error) = self.apply_color_option() {
+            self.report_or_collect(error, &mut errors, report)?;
+        }
+        if let Some(command_struct) = self.get_default_command() {
+            let mut args = vec![command_struct.name.to_string()];
+            args.extend(self.args.iter().skip(1).cloned());
+            return self.dispatch(command_struct, args, 0, errors, report);
+        }
+        if let Some(unmatched_callback) = self.get_unmatched_callback() {
+            self.report_collected(errors, report)?;
+            unmatched_callback(self, &self.args[1..]);
+            return Ok(self.action.get());
+        }
+        if self.arg_required_else_help && self.parsed_args().len() <= 1 {
+            self.report_collected(errors, report)?;
+            self.default_help();
+            return Ok(self.action.get());
+        }
+        let first_positional = self.positional_indexes().first().copied();
+        // skip the app runner / command
+        'args: for (index, _arg) in self.parsed_args().iter().enumerate().skip(1) {
+            let mut arg = _arg.to_string();
+            let mut current_callback: Option<fn(&Fli)> = None;
+
+            if !self.is_option_token(&arg) {
+                if self.prefer_positionals_after_args && Some(index) != first_positional {
+                    continue;
+                }
+                if let Some(command_struct) = self.find_command(arg.trim()) {
+                    self.warn_if_deprecated(arg.trim());
+                    let args = self.args[index..].to_vec();
+                    return self.dispatch(command_struct, args, index, errors, report);
+                }
+                continue;
+            }
+            let candidates = self.abbreviation_candidates(&arg);
+            if candidates.len() > 1 {
+                let error = FliError::AmbiguousOption {
+                    option: arg,
+                    candidates,
+                    index: self.arg_offset + index,
+                };
+                self.report_or_collect(error, &mut errors, report)?;
+                continue;
+            }
+            arg = self.get_callable_name(arg);
+            for optional_template in ["", "[]", "[...]"] {
+                // check if it need a required param
+                let find = &format!("{arg} {optional_template}");
+                let callback_find = self.args_hash_table.get(find.trim());
+                if callback_find.is_none() {
+                    continue;
+                }
+                current_callback = callback_find.copied();
+            }
+            for required_template in ["<>", "<...>"] {
+                // check if it need a required param
+                let find = &format!("{arg} {required_template}");
+                let callback_find = self.args_hash_table.get(find.trim());
+                if callback_find.is_none() {
+                    continue;
+                }
+                // make sure a value is passed in else it should show error/help
+                if !self.has_a_value(arg.trim().to_string()) {
+                    let error = FliError::MissingValue {
+                        option: arg,
+                        index: self.arg_offset + index,
+                    };
+                    self.report_or_collect(error, &mut errors, report)?;
+                    continue 'args;
+                }
+                current_callback = callback_find.copied();
+            }
+            if arg == "--color" && self.builtin_color && current_callback.is_some() {
+                // already applied before any callback
+                continue;
+            }
+            if arg == "--config" && self.config_file.is_some() && current_callback.is_some() {
+                // already loaded before any callback
+                continue;
+            }
+            if self.verbosity_flags && (arg == "--verbose" || arg == "--quiet") && current_callback.is_some() {
+                // read by `verbosity` when the callbacks run
+                continue;
+            }
+            if current_callback.is_some() {
+                self.warn_if_deprecated(&arg);
+                if *_arg != arg {
+                    // a hidden alias
+                    self.warn_if_deprecated(_arg);
+                }
+            }
+
+            let Some(current_callback) = current_callback else {
+                match self.unknown_options {
+                    UnknownOptionPolicy::Error => {}
+                    UnknownOptionPolicy::Warn => {
+                        if !self.is_registered_and_passed("--quiet") {
+                            display::print_warning(&fill(
+                                &messages::messages().ignoring_unknown_option,
+                                &[("option", _arg)],
+                            ));
+                        }
+                        continue;
+                    }
+                    UnknownOptionPolicy::Collect => continue,
+                }
+                let error = FliError::UnknownOption {
+                    suggestions: self.get_most_similar_options(_arg),
+                    option: _arg.to_string(),
+                    index: self.arg_offset + index,
+                };
+                self.report_or_collect(error, &mut errors, report)?;
+                continue;
+            };
+
+            if !callbacks.iter().any(|(_, c)| *c as usize == current_callback as usize)
+                || self.allow_duplicate_callback
+            {
+                let priority = self.option_priorities.get(&arg).copied().unwrap_or_default();
+                callbacks.push((priority, current_callback))
+            }
+        }
+        // no command was passed, or it would have run
+        if self.subcommand_required
+            && !self.is_passed("--help".to_string())
+            && !self.is_passed("--version".to_string())
+        {
+            let error = FliError::MissingSubcommand {
+                command: self.name.to_string(),
+            };
+            self.report_or_collect(error, &mut errors, report)?;
+        }
+        if let Err(error) = self.check_exclusive_options() {
+            self.report_or_collect(error, &mut errors, report)?;
+        }
+        if !self.is_passed("--help".to_string()) && !self.is_passed("--version".to_string()) {
+            for error in self.option_value_errors() {
+                self.report_or_collect(error, &mut errors, report)?;
+            }
+            if let Err(error) = self.check_positional_args() {
+                self.report_or_collect(error, &mut errors, report)?;
+            }
+            for error in self.validation_errors() {
+                self.report_or_collect(error, &mut errors, report)?;
+            }
+        }
+        self.report_collected(errors, report)?;
+        if callbacks.is_empty() {
+            callbacks.push((0, self.default_callback));
+        }
+        // stable, so the same priority keeps the order passed
+        callbacks.sort_by_key(|(priority, _)| std::cmp::Reverse(*priority));
+        self.run_callbacks(callbacks.into_iter().map(|(_, callback)| callback).collect());
+        Ok(self.action.get())
+    }
+
+    /// Checks the number of positional args passed against the minimum and maximum of the command
+    fn check_positional_args(&self) -> Result<(), FliError> {
+        let indexes = self.positional_indexes();
+        if indexes.len() < self.min_positional_args {
+            return Err(FliError::MissingArguments {
+                command: self.name.to_string(),
+                expected: self.min_positional_args,
+                got: indexes.len(),
+                usage: self.usage_line(),
+            });
+        }
+        match self.max_positional_args {
+            Some(max) if indexes.len() > max => Err(FliError::TooManyArguments {
+                argument: self.args[indexes[max]].to_string(),
+                expected: max,
+                got: indexes.len(),
+                index: self.arg_offset + indexes[max],
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks the values of the options against their type and `value_count`, in the order they were passed
+    fn option_value_errors(&self) -> Vec<FliError> {
+        let messages = messages::messages();
+        let mut errors = vec![];
+        let mut counts: Vec<_> = self.value_counts.iter().collect();
+        counts.sort_by_key(|(long, _)| self.get_arg_index(long));
+        for (long, &(min, max)) in counts {
+            if !self.is_passed_in_args(long) {
+                continue;
+            }
+            let got = self.get_values(long.to_string()).map_or(0, |values| values.len());
+            let message = if max == Some(min) && got != min {
+                fill(&messages.wrong_value_count, &[("count", &min.to_string()), ("got", &got.to_string())])
+            } else if got < min {
+                fill(&messages.too_few_values, &[("min", &min.to_string()), ("got", &got.to_string())])
+            } else if let Some(max) = max.filter(|max| got > *max) {
+                fill(&messages.too_many_values, &[("max", &max.to_string()), ("got", &got.to_string())])
+            } else {
+                continue;
+            };
+            errors.push(FliError::InvalidValue {
+                option: long.to_string(),
+                message,
+                index: self.get_arg_index(long).map_or(0, |index| index + 1),
+            });
+        }
+        let mut checks: Vec<_> = self.value_checks.iter().collect();
+        checks.sort_by_key(|(long, _)| self.get_arg_index(long));
+        for (long, check) in checks {
+            let Ok(values) = self.get_values(long.to_string()) else {
+                continue;
+            };
+            // the first value that does not parse, once per option
+            if let Some(message) = values.iter().find_map(|value| check(value).err()) {
+                errors.push(FliError::InvalidValue {
+                    option: long.to_string(),
+                    message,
+                    index: self.get_arg_index(long).map_or(0, |index| index + 1),
+                });
+            }
+        }
+        errors
+    }
+
+    /// Checks that the exclusive options passed are the only args, see `exclusive`
+    fn check_exclusive_options(&self) -> Result<(), FliError> {
+        let args = self.parsed_args();
+        let Some(option) = args
+            .iter()
+            .skip(1)
+            .filter(|arg| self.is_option_token(arg))
+            .map(|arg| self.get_callable_name(arg.to_string()))
+            .find(|long| self.exclusive_options.contains(long))
+        else {
+            return Ok(());
+        };
+        let other_option = args
+            .iter()
+            .skip(1)
+            .position(|arg| self.is_option_token(arg) && self.get_callable_name(arg.to_string()) != option)
+            .map(|position| position + 1);
+        let first_positional = self.positional_indexes().first().copied();
+        match [other_option, first_positional].into_iter().flatten().min() {
+            Some(index) => Err(FliError::ExclusiveOption {
+                option,
+                other: self.args[index].to_string(),
+                index: self.arg_offset + index,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Runs the validators of the command as the errors are read, so stopping at the first error skips the others
+    fn validation_errors(&self) -> impl Iterator<Item = FliError> + '_ {
+        self.validators.iter().filter_map(|validator| {
+            validator(self).err().map(|message| FliError::ValidationFailed {
+                command: self.name.to_string(),
+                message,
+            })
+        })
+    }
+
+    /// Reports an error and returns it, or keeps it in `errors` to report with the others with `collect_errors`
+    fn report_or_collect(
+        &self,
+        error: FliError,
+        errors: &mut Vec<FliError>,
+        report: fn(&Fli, &FliError),
+    ) -> Result<(), FliError> {
+        if self.collect_errors {
+            errors.push(error);
+            return Ok(());
+        }
+        report(self, &error);
+        Err(error)
+    }
+
+    /// Reports the errors kept with `collect_errors` together, as one `FliError::Multiple` when there are several
+    fn report_collected(&self, mut errors: Vec<FliError>, report: fn(&Fli, &FliError)) -> Result<(), FliError> {
+        let error = match errors.len() {
+            0 => return Ok(()),
+            1 => errors.remove(0),
+            _ => FliError::Multiple { errors },
+        };
+        report(self, &error);
+        Err(error)
+    }
+
+    /// Gets the app with the `@file` args replaced by the args in the files
+    /// none when response files are not allowed or there are none, commands get the args already replaced
+    fn expand_response_files(&self) -> Option<Result<Fli, FliError>> {
+        if !self.response_files || !self.command_line.is_empty() {
+            return None;
+        }
+        let is_response_file = |arg: &String| arg.len() > 1 && arg.starts_with('@');
+        let end = self.args.iter().position(|arg| arg == "--").unwrap_or(self.args.len());
+        if !self.args.get(1..end)?.iter().any(is_response_file) {
+            return None;
+        }
+        let mut args = vec![];
+        for (index, arg) in self.args.iter().enumerate() {
+            if index == 0 || index >= end || !is_response_file(arg) {
+                args.push(arg.to_string());
+                continue;
+            }
+            match read_response_file(&arg[1..], 0) {
+                Ok(file_args) => args.extend(file_args),
+                Err(message) => {
+                    return Some(Err(FliError::InvalidValue {
+                        option: arg.to_string(),
+                        message,
+                        index: self.arg_offset + index,
+                    }))
+                }
+            }
+        }
+        let mut app = self.clone();
+        app.set_args(args);
+        // the args in the files are not response files again
+        app.response_files = false;
+        Some(Ok(app))
+    }
+
+    /// Gets the app with the Windows style options of the command, see `allow_slash_options`,
+    /// and `--color=when` replaced by the usual ones
+    /// none when they are not allowed or there are none. The args of commands are left to them
+    fn expand_option_forms(&self) -> Option<Fli> {
+        if !self.slash_options && !self.builtin_color {
+            return None;
+        }
+        // the args from the first command on are read by the command
+        let command = self.args.iter().skip(1).position(|arg| self.find_command(arg.trim()).is_some());
+        let end = self
+            .trailing_args_index()
+            .into_iter()
+            .chain(command.map(|index| index + 1))
+            .min()
+            .unwrap_or(self.args.len());
+        let mut expanded = false;
+        let mut args = vec![];
+        for (index, arg) in self.args.iter().enumerate() {
+            let option_args = match self.slash_options {
+                true => self.slash_option(arg),
+                false => None,
+            };
+            match option_args.or_else(|| self.color_option_value(arg)) {
+                Some(option_args) if index > 0 && index < end => {
+                    args.extend(option_args);
+                    expanded = true;
+                }
+                _ => args.push(arg.to_string()),
+            }
+        }
+        if !expanded {
+            return None;
+        }
+        let mut app = self.clone();
+        app.set_args(args);
+        Some(app)
+    }
+
+    /// Gets the usual args for `--color=when` when the built-in `--color` option is registered
+    fn color_option_value(&self, arg: &str) -> Option<Vec<String>> {
+        let value = arg.strip_prefix("--color=").filter(|_| self.builtin_color)?;
+        Some(vec!["--color".to_string(), value.to_string()])
+    }
+
+    /// Gets the usual args for a Windows style option of the command, like `--output file.txt` for `/output:file.txt`
+    fn slash_option(&self, arg: &str) -> Option<Vec<String>> {
+        let option = arg.strip_prefix('/')?;
+        if option == "?" {
+            return Some(vec!["--help".to_string()]).filter(|_| self.get_param_type("--help").is_some());
+        }
+        let (name, value) = match option.split_once([':', '=']) {
+            Some((name, value)) => (name, Some(value)),
+            None => (option, None),
+        };
+        let option = match name.chars().count() {
+            0 => return None,
+            1 => format!("-{name}"),
+            _ => format!("--{name}"),
+        };
+        self.get_param_type(&self.get_callable_name(option.to_string()))?;
+        Some([option].into_iter().chain(value.map(str::to_string)).collect())
+    }
+
+    /// Gets the whole command line, the runner first
+    fn get_command_line(&self) -> &[String] {
+        if self.command_line.is_empty() {
+            &self.args
+        } else {
+            &self.command_line
+        }
+    }
+
+    /// Gets the position of an option in the command line
+    fn get_arg_index(&self, option: &str) -> Option<usize> {
+        let index = self
+            .parsed_args()
+            .iter()
+            .position(|arg| self.get_callable_name(arg.to_string()) == option)?;
+        Some(self.arg_offset + index)
+    }
+
+    /// Runs a command of the app on the given args, the first one being the command name
+    /// `offset` is the position of the command in the args of this app and `errors` the ones collected before it
+    fn dispatch(
+        &self,
+        command_struct: &Fli,
+        args: Vec<String>,
+        offset: usize,
+        errors: Vec<FliError>,
+        report: fn(&Fli, &FliError),
+    ) -> Result<Action, FliError> {
+        let mut command = command_struct.clone();
+        command.args = args;
+        command.parent_errors = errors;
+        command.command_line = self.get_command_line().to_vec();
+        command.arg_offset = self.arg_offset + offset;
+        // the options after the command are its own
+        let own_args = &self.parsed_args()[..offset.min(self.parsed_args().len())];
+        command.parent_verbosity = self.parent_verbosity.saturating_add(self.passed_verbosity(own_args));
+        command.config_section = [self.config_section.as_slice(), &[command.name.clone()]].concat();
+        if !command.is_passed_in_args("--config") {
+            // the file of the app, read once
+            command.config = self.config.clone();
+            command.config_file = command.config_file.or(self.config_file.clone());
+        }
+        command.help_pager |= self.help_pager;
+        command.error_handler = command.error_handler.or(self.error_handler);
+        command.value_terminator = command.value_terminator.take().or(self.value_terminator.clone());
+        for (kind, code) in &self.exit_codes {
+            command.exit_codes.entry(*kind).or_insert(*code);
+        }
+        command.inherit_help(self);
+        if command.error_renderer.is_none() {
+            command.error_renderer = self.error_renderer.clone();
+        }
+        let mut parent = self.clone();
+        parent.args.truncate(offset.max(1));
+        command.parent = Some(Box::new(parent));
+        // the hooks of the parent wrap the ones of the command
+        command.before_each_hooks =
+            [self.before_each_hooks.as_slice(), &command.before_each_hooks].concat();
+        command.after_each_hooks =
+            [command.after_each_hooks.as_slice(), &self.after_each_hooks].concat();
+        // with the verbosity flags the command reads the quiet options of its parents from its verbosity
+        let _quiet = display::scoped_quiet(!self.verbosity_flags && self.is_registered_and_passed("--quiet"));
+        command.execute(report)
+    }
+
+    /// Gets the unmatched callback if it should run, that is when the first arg is a value that is not a command
+    /// and no default callback was set to take it
+    fn get_unmatched_callback(&self) -> Option<fn(&Fli, &[String])> {
+        let unmatched_callback = self.unmatched_callback?;
+        let first = self.parsed_args().get(1)?;
+        let is_default = self.default_callback as usize == fli_default_callback as fn(&Fli) as usize;
+        let is_unmatched = !self.is_option_token(first) && self.find_command(first.trim()).is_none();
+        (is_default && is_unmatched).then_some(unmatched_callback)
+    }
+
+    /// Gets the default command if it should run, that is when no command and no option of the app is passed
+    fn get_default_command(&self) -> Option<&Fli> {
+        let command = self
+            .cammands_hash_tables
+            .get(self.default_command.as_deref()?)?;
+        let passes_own_arg = self.parsed_args().iter().skip(1).any(|arg| {
+            if self.is_option_token(arg) {
+                self.get_param_type(&self.get_callable_name(arg.to_string())).is_some()
+            } else {
+                self.find_command(arg.trim()).is_some()
+            }
+        });
+        (!passes_own_arg).then_some(command)
+    }
+
+    pub fn has_a_value(&self, arg_name: String) -> bool {
+        let binding = self.get_callable_name(arg_name);
+        let arg_full_name = binding.trim();
+        let args = self.parsed_args();
+        for (counter, arg) in args.iter().enumerate() {
+            if self.get_callable_name(arg.to_string()) == arg_full_name {
+                if let Some(value) = args.get(counter + 1) {
+                    if !self.ends_values(value, self.get_param_type(arg_full_name).unwrap_or("")) {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    fn run_callbacks(&self, callbacks: Vec<for<'a> fn(&'a Fli)>) -> &Self {
+        let _prompt_flags = prompt::scoped_flags(
+            self.is_registered_and_passed("--yes"),
+            self.is_registered_and_passed("--no-input"),
+        );
+        let quiet = match self.verbosity_flags {
+            true => self.verbosity() < 0,
+            false => self.is_registered_and_passed("--quiet"),
+        };
+        let _quiet = display::scoped_quiet(quiet);
+        let _verbosity = display::scoped_verbosity(self.verbosity());
+        let hooks_before = self.before_each_hooks.iter().chain(&self.before_hooks);
+        for callback in hooks_before.chain(&callbacks) {
+            callback(self);
+            if self.action.get() != Action::Continue {
+                break;
+            }
+        }
+        // cleanup and timing hooks run even when a hook or callback asked to stop
+        for hook in self.after_hooks.iter().chain(&self.after_each_hooks) {
+            hook(self);
+        }
+        self
+    }
+    /**
+     * Gets the Long name for a short arg
+     */
+    pub fn get_callable_name(&self, arg: String) -> String {
+        if let Some(long_name) = self.option_names.get(&arg) {
+            return long_name.to_string();
+        }
+        let mut arg_template: String = arg.to_string();
+        if !arg_template.starts_with("-") {
+            arg_template = format!("-{}", arg);
+        }
+        if let Some(long_name) = self.short_hash_table.get(&arg_template) {
+            arg_template = long_name.to_string();
+        }
+        if !arg_template.starts_with("--") {
+            arg_template = format!("--{}", arg);
+        } else if let [long_name] = self.abbreviation_candidates(&arg_template).as_slice() {
+            arg_template = long_name.to_string();
+        }
+        if let Some(long_name) = self.separator_match(&arg_template) {
+            arg_template = long_name;
+        }
+        arg_template
+    }
+
+    /// Gets the long option a long option with `_` and `-` swapped stands for
+    /// none when either separator is not allowed or the option is registered as is
+    fn separator_match(&self, arg: &str) -> Option<String> {
+        if !self.allow_any_separator || !arg.starts_with("--") || !arg[2..].contains(['-', '_']) {
+            return None;
+        }
+        let long_names = self.get_long_option_names();
+        if long_names.iter().any(|name| name == arg) {
+            return None;
+        }
+        let normalized = arg.replace('_', "-");
+        long_names.into_iter().find(|name| name.replace('_', "-") == normalized)
+    }
+
+    /// Gets the long names of the options of the app, like `--name`
+    fn get_long_option_names(&self) -> Vec<String> {
+        self.args_hash_table
+            .keys()
+            .filter_map(|key| key.split_whitespace().next())
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    /// Gets the long options an abbreviated long option could stand for
+    /// empty when abbreviations are not allowed or the option is registered as is
+    fn abbreviation_candidates(&self, arg: &str) -> Vec<String> {
+        if !self.allow_abbreviations || !arg.starts_with("--") || arg == "--" {
+            return vec![];
+        }
+        let mut long_names = self.get_long_option_names();
+        if long_names.iter().any(|name| name == arg) {
+            return vec![];
+        }
+        long_names.retain(|name| name.starts_with(arg));
+        long_names.sort();
+        long_names
+    }
+    pub fn get_values(&self, arg: String) -> Result<Vec<String>, &str> {
+        let arg_name: String = self.get_callable_name(arg);
+        // if the argument does not need a param then dont return none
+        if self.args_hash_table.contains_key(&arg_name) {
+            return Err("Does not expect a value");
+        }
+        let args = self.parsed_args();
+        let action = self.arg_actions.get(&arg_name).copied();
+        // the values of each occurrence of the option
+        let mut occurrences: Vec<Vec<String>> = vec![];
+        let mut counter = 1;
+        for i in args {
+            let i = self.get_callable_name(i.to_string());
+            if i != arg_name {
+                counter += 1;
+                continue;
+            }
+            let mut found = vec![];
+            let mut single = false;
+            for template in ["[]", "<>"] {
+                let binding = &format!("{} {template}", arg_name);
+                if self.args_hash_table.contains_key(binding) {
+                    if let Some(v) = args.get(counter) {
+                        if self.is_option_token(v) {
+                            return Err("No value passed");
+                        }
+                        found.push(v.to_string());
+                        single = true;
+                    }
+                }
+            }
+            for template in ["[...]", "<...>"] {
+                let binding = &format!("{} {template}", arg_name);
+                if self.args_hash_table.contains_key(binding) {
+                    if let Some(params) = args.get((counter)..args.len()) {
+                        for i in params {
+                            if self.ends_values(i, template) {
+                                break;
+                            }
+                            found.push(i.to_string());
+                        }
+                    }
+                }
+            }
+            occurrences.push(found);
+            // the first value is kept unless the action reads the other occurrences
+            if single && !matches!(action, Some(ArgAction::Set | ArgAction::Append)) {
+                break;
+            }
+            counter += 1;
+        }
+        let values = match action {
+            Some(ArgAction::Set) => occurrences
+                .into_iter()
+                .rev()
+                .find(|values| !values.is_empty())
+                .unwrap_or_default(),
+            _ => occurrences.concat(),
+        };
+        if !values.is_empty() {
+            return Ok(values);
+        }
+        if !self.is_passed_in_args(&arg_name) {
+            if let Some(values) = self.get_config_values(&arg_name) {
+                return Ok(values);
+            }
+            if let Some(default) = self.dynamic_defaults.get(&arg_name) {
+                return Ok(vec![default(self)]);
+            }
+            if let Some(default) = self.default_values.get(&arg_name) {
+                return Ok(vec![default.to_string()]);
+            }
+        }
+        Err("No value passed")
+    }
+
+    /// Gets the `key=value` pairs passed to an option as a map
+    /// every occurrence of the option is collected, so `-D name=fli -D mode=dev` gives both pairs
+    /// # Arguments
+    /// * `arg` - The option to get the pairs for (`-D`, `--define`, `D` or `define`)
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-D --define, <>", "Define a build variable", |x| {
+    ///    if let Ok(defines) = x.get_map("define".to_string()) {
+    ///       for (key, value) in defines {
+    ///          fli_println!("{key} = {value}");
+    ///       }
+    ///    }
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `Result<HashMap<String, String>, &str>` - The pairs or an error message if the option has none or a value is not a pair
+    pub fn get_map(&self, arg: String) -> Result<HashMap<String, String>, &str> {
+        let arg_name: String = self.get_callable_name(arg);
+        let param_type = match self.get_param_type(&arg_name) {
+            Some("") | None => return Err("Does not expect a value"),
+            Some(param_type) => param_type,
+        };
+        let mut map: HashMap<String, String> = HashMap::new();
+        let args = self.parsed_args();
+        for (counter, i) in args.iter().enumerate() {
+            if self.get_callable_name(i.to_string()) != arg_name {
+                continue;
+            }
+            let mut values: Vec<&String> = vec![];
+            for value in args.iter().skip(counter + 1) {
+                if self.ends_values(value, param_type) {
+                    break;
+                }
+                values.push(value);
+                if !param_type.contains("...") {
+                    break;
+                }
+            }
+            for value in values {
+                match value.split_once('=') {
+                    Some((key, value)) if !key.is_empty() => {
+                        map.insert(key.to_string(), value.to_string());
+                    }
+                    _ => return Err("Expected a key=value pair"),
+                }
+            }
+        }
+        if map.is_empty() {
+            return Err("No value passed");
+        }
+        Ok(map)
+    }
+
+    /// Gets the value of an option as a `Duration`, accepting human strings like `30s`, `5m` or `2h30m`
+    /// # Arguments
+    /// * `arg` - The option to get the duration for (`-t`, `--timeout`, `t` or `timeout`)
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-t --timeout, []", "How long to wait", |x| {
+    ///    let timeout = x.get_duration("timeout".to_string()).unwrap_or(Duration::from_secs(30));
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `Result<Duration, &str>` - The duration or an error message if no valid duration was passed
+    pub fn get_duration(&self, arg: String) -> Result<Duration, &str> {
+        let values = self.get_values(arg)?;
+        match values.first().and_then(|value| parse_duration(value)) {
+            Some(duration) => Ok(duration),
+            None => Err("Invalid duration, expected a value like 30s, 5m or 2h30m"),
+        }
+    }
+
+    /// Gets the value of an option as a number of bytes, accepting human sizes like `512`, `4K` or `1.5MiB`
+    /// # Arguments
+    /// * `arg` - The option to get the size for (`-s`, `--size`, `s` or `size`)
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-s --size, <>", "Minimum file size", |x| {
+    ///    if let Ok(bytes) = x.get_size("size".to_string()) {
+    ///       fli_println!("Looking for files over {bytes} bytes");
+    ///    }
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `Result<u64, &str>` - The size in bytes or an error message if no valid size was passed
+    pub fn get_size(&self, arg: String) -> Result<u64, &str> {
+        let values = self.get_values(arg)?;
+        match values.first().and_then(|value| parse_size(value)) {
+            Some(size) => Ok(size),
+            None => Err("Invalid size, expected a value like 512, 4K or 1.5MiB"),
+        }
+    }
+
+    /// Gets the value of an option parsed into any type implementing `FliValue`
+    /// # Arguments
+    /// * `arg` - The option to get the value for (`-p`, `--port`, `p` or `port`)
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-p --port, <>", "Port to listen on", |x| {
+    ///    match x.get_value::<u16>("port".to_string()) {
+    ///       Ok(port) => fli_println!("Listening on {port}"),
+    ///       Err(e) => x.print_help(&e),
+    ///    }
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `Result<T, String>` - The parsed value or an error message naming the expected type
+    pub fn get_value<T: FliValue>(&self, arg: String) -> Result<T, String> {
+        let arg_name = self.get_callable_name(arg.clone());
+        let values = self.get_values(arg).map_err(|e| e.to_string())?;
+        let value = values.first().ok_or("No value passed")?;
+        T::parse(value).map_err(|e| format!("Invalid {} for {arg_name}: {e}", T::type_name()))
+    }
+
+    /// Gets every value of an option parsed into any type implementing `FliValue`, for options taking many values
+    /// # Arguments
+    /// * `arg` - The option to get the values for (`-p`, `--ports`, `p` or `ports`)
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-p --ports, <...>", "Ports to listen on", |x| {
+    ///    if let Ok(ports) = x.get_value_list::<u16>("ports".to_string()) {
+    ///       fli_println!("Listening on {} ports", ports.len());
+    ///    }
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `Result<Vec<T>, String>` - The parsed values or an error message naming the expected type and the first bad value
+    pub fn get_value_list<T: FliValue>(&self, arg: String) -> Result<Vec<T>, String> {
+        let arg_name = self.get_callable_name(arg.clone());
+        let values = self.get_values(arg).map_err(|e| e.to_string())?;
+        values
+            .iter()
+            .map(|value| T::parse(value).map_err(|e| format!("Invalid {} for {arg_name}: {e}", T::type_name())))
+            .collect()
+    }
+
+    /// Gets the value of an option as an integer, the same as `get_value::<i64>`
+    /// # Arguments
+    /// * `arg` - The option to get the value for (`-l`, `--level`, `l` or `level`)
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-l --level, []", "Compression level", |x| {
+    ///    let level = x.get_int("level".to_string()).unwrap_or(6);
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `Result<i64, String>` - The integer or an error message
+    pub fn get_int(&self, arg: String) -> Result<i64, String> {
+        self.get_value(arg)
+    }
+
+    /// Gets the value of an option as a float, the same as `get_value::<f64>`
+    /// # Arguments
+    /// * `arg` - The option to get the value for (`-r`, `--ratio`, `r` or `ratio`)
+    ///
+    /// # Returns
+    /// * `Result<f64, String>` - The float or an error message
+    pub fn get_float(&self, arg: String) -> Result<f64, String> {
+        self.get_value(arg)
+    }
+
+    /// Gets the value of an option as a bool, accepting `true`/`false`, `yes`/`no`, `on`/`off` and `1`/`0`
+    /// use `get_flag` or `is_passed` for options without a value
+    /// # Arguments
+    /// * `arg` - The option to get the value for (`-c`, `--cache`, `c` or `cache`)
+    ///
+    /// # Returns
+    /// * `Result<bool, String>` - The bool or an error message
+    pub fn get_bool(&self, arg: String) -> Result<bool, String> {
+        self.get_value(arg)
+    }
+
+    /// Gets the value of an option as a path, the same as `get_value::<PathBuf>`
+    /// # Arguments
+    /// * `arg` - The option to get the value for (`-o`, `--output`, `o` or `output`)
+    ///
+    /// # Returns
+    /// * `Result<PathBuf, String>` - The path or an error message
+    pub fn get_path(&self, arg: String) -> Result<PathBuf, String> {
+        self.get_value(arg)
+    }
+
+    /// Checks if a token should be read as an option rather than a value
+    /// negative numbers like `-5` or `-1.5`, and any token with `allow_hyphen_values`, are values unless an option is registered with that name
+    fn is_option_token(&self, token: &str) -> bool {
+        // `-` alone stands for the standard input
+        if !token.starts_with('-') || token == "-" {
+            return false;
+        }
+        if token == "--" || !(self.allow_hyphen_values || is_negative_number(token)) {
+            return true;
+        }
+        self.short_hash_table.contains_key(token)
+            || self
+                .get_param_type(&self.get_callable_name(token.to_string()))
+                .is_some()
     }
-    pub fn get_params_callback(&mut self, key: String) -> Option<&for<'a> fn(&'a Fli)> {
-        if let Some(callback) = self.args_hash_table.get(&self.get_callable_name(key)) {
-            return Some(callback);
+
+    /// Checks if a token ends the values of an option with the given param type template,
+    /// an option does and so does the value terminator for options taking many values
+    fn ends_values(&self, token: &str, param_type: &str) -> bool {
+        self.is_option_token(token) || self.is_value_terminator(token, param_type)
+    }
+
+    /// Checks if a token is the value terminator ending the values of an option with the given param type template
+    fn is_value_terminator(&self, token: &str, param_type: &str) -> bool {
+        param_type.contains("...") && self.value_terminator.as_deref() == Some(token)
+    }
+
+    /// Gets the args after `--`, left untouched, or when `allow_trailing_args` is on
+    /// the args from the first value that is not an option, an option value or a command
+    ///
+    /// # Example
+    /// ```
+    /// // $ mytool exec -- cargo test --release
+    /// let program = x.trailing_args(); // ["cargo", "test", "--release"]
+    /// ```
+    ///
+    /// # Returns
+    /// * `Vec<String>` - The trailing args, empty if there are none
+    pub fn trailing_args(&self) -> Vec<String> {
+        match self.trailing_args_index() {
+            Some(index) if self.args[index] == "--" => self.args[index + 1..].to_vec(),
+            Some(index) => self.args[index..].to_vec(),
+            None => vec![],
         }
-        return None;
     }
-    pub fn run(&self) -> &Fli {
-        let mut callbacks: Vec<for<'a> fn(&'a Fli)> = vec![];
-        let mut init_arg = self.args.clone();
-        init_arg.remove(0); // remove the app runner / command
-        let default_callback: fn(&Fli) = fli_default_callback;
-        for _arg in init_arg {
-            let mut arg = _arg;
-            let mut current_callback = default_callback;
-
-            if !arg.starts_with("-") {
-                if let Some(command_struct) = self.cammands_hash_tables.get(arg.trim()) {
-                    return command_struct.run();
-                }
+
+    /// Counts how many times an option the app has is in the args
+    fn count_registered_passed(&self, option: &str, args: &[String]) -> usize {
+        if self.get_param_type(option).is_none() {
+            return 0;
+        }
+        args.iter()
+            .skip(1)
+            .filter(|arg| self.is_option_token(arg) && self.get_callable_name(arg.to_string()) == option)
+            .count()
+    }
+
+    /// Gets the verbosity from the `--verbose`, `--debug` and `--quiet` options in the args, see `display::set_verbosity`
+    fn passed_verbosity(&self, args: &[String]) -> i8 {
+        let count = |option| self.count_registered_passed(option, args).min(i8::MAX as usize) as i8;
+        let mut verbosity = count("--verbose");
+        if count("--debug") > 0 {
+            verbosity = verbosity.max(2);
+        }
+        verbosity.saturating_sub(count("--quiet"))
+    }
+
+    /// Checks if the app has an option and it is passed, for options fli gives a meaning to like `--quiet`
+    fn is_registered_and_passed(&self, option: &str) -> bool {
+        self.get_param_type(option).is_some() && self.is_passed(option.to_string())
+    }
+
+    /// Gets the options passed that the app does not have, when `allow_unknown_args` is on
+    /// or the unknown option policy is `Collect`
+    /// the values after them are read like any other value, pass them as `--option=value` to keep them together
+    ///
+    /// # Returns
+    /// * `Vec<String>` - The unknown options as passed, empty if there are none or unknown options are not allowed
+    pub fn unknown_args(&self) -> Vec<String> {
+        if self.unknown_options != UnknownOptionPolicy::Collect {
+            return vec![];
+        }
+        self.parsed_args()
+            .iter()
+            .skip(1)
+            .filter(|arg| {
+                self.is_option_token(arg)
+                    && self.get_param_type(&self.get_callable_name(arg.to_string())).is_none()
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Gets the positional args, the values that are not options, option values or commands, then the trailing args
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-o --output, <>", "The output file", |x| {});
+    /// // $ app a.txt -o out.txt b.txt -- -c.txt
+    /// let files = x.positional_args(); // ["a.txt", "b.txt", "-c.txt"]
+    /// ```
+    ///
+    /// # Returns
+    /// * `Vec<String>` - The positional args in the order they were passed
+    pub fn positional_args(&self) -> Vec<String> {
+        self.positional_indexes()
+            .into_iter()
+            .map(|index| self.args[index].to_string())
+            .collect()
+    }
+
+    /// Gets the positions of the positional args in the args
+    fn positional_indexes(&self) -> Vec<usize> {
+        let args = self.parsed_args();
+        let mut indexes = vec![];
+        let mut index = 1;
+        while index < args.len() {
+            let arg = &args[index];
+            index += 1;
+            if !self.is_option_token(arg) {
+                indexes.push(index - 1);
                 continue;
             }
-            arg = self.get_callable_name(arg);
-            for optional_template in ["", "[]", "[...]"] {
-                // check if it need a required param
-                let find = &String::from(format!("{arg} {optional_template}"));
-                let callback_find = self.args_hash_table.get(find.trim());
-                if callback_find.is_none() {
-                    continue;
+            // skip the values the option takes
+            let param_type = self
+                .get_param_type(&self.get_callable_name(arg.to_string()))
+                .unwrap_or("");
+            while !param_type.is_empty()
+                && index < args.len()
+                && !self.ends_values(&args[index], param_type)
+            {
+                index += 1;
+                if !param_type.contains("...") {
+                    break;
                 }
-                current_callback = *callback_find.unwrap();
             }
-            for required_template in ["<>", "<...>"] {
-                // check if it need a required param
-                let find = &String::from(format!("{arg} {required_template}"));
-                let callback_find = self.args_hash_table.get(find.trim());
-                if callback_find.is_none() {
-                    continue;
+            if args.get(index).is_some_and(|arg| self.is_value_terminator(arg, param_type)) {
+                index += 1;
+            }
+        }
+        let trailing_start = match self.trailing_args_index() {
+            Some(index) if self.args[index] == "--" => index + 1,
+            Some(index) => index,
+            None => self.args.len(),
+        };
+        indexes.extend(trailing_start..self.args.len());
+        indexes
+    }
+
+    /// Gets the index where trailing args start (the `--` separator, the first unknown value when allowed
+    /// or the last positional arg when it captures the rest)
+    fn trailing_args_index(&self) -> Option<usize> {
+        let captures_rest = self.last_positional_captures_rest && !self.arguments.is_empty();
+        if !self.allow_trailing_args && !captures_rest {
+            // `--` ends the options wherever it is
+            return self.args.iter().skip(1).position(|arg| arg == "--").map(|index| index + 1);
+        }
+        let mut positionals = 0;
+        let mut index = 1;
+        while index < self.args.len() {
+            let arg = &self.args[index];
+            if arg == "--" {
+                return Some(index);
+            }
+            index += 1;
+            if !self.is_option_token(arg) {
+                if positionals == 0 && self.find_command(arg.trim()).is_some() {
+                    return None;
                 }
-                // make sure a value is passed in else it should show error/help
-                if !self.has_a_value(arg.trim().to_string()) {
-                    self.print_help(&format!("Invalid syntax : {arg}  does not have a value"));
-                    return self;
+                // with `last_positional_captures_rest` the positional args before the last one are read as usual
+                if self.allow_trailing_args || positionals + 1 == self.arguments.len() {
+                    return Some(index - 1);
                 }
-                current_callback = *(callback_find.unwrap());
+                positionals += 1;
+                continue;
             }
-
-            if current_callback == default_callback {
-                callbacks = Vec::new();
-                // break;
+            // skip the values the option takes
+            let param_type = self
+                .get_param_type(&self.get_callable_name(arg.to_string()))
+                .unwrap_or("");
+            while !param_type.is_empty()
+                && index < self.args.len()
+                && self.args[index] != "--"
+                && !self.ends_values(&self.args[index], param_type)
+            {
+                index += 1;
+                if !param_type.contains("...") {
+                    break;
+                }
             }
-
-            if !callbacks.contains(&current_callback) || self.allow_duplicate_callback {
-                callbacks.push(current_callback)
+            if self.args.get(index).is_some_and(|arg| self.is_value_terminator(arg, param_type)) {
+                index += 1;
             }
         }
-        if callbacks.len() == 0 {
-            callbacks.push(self.default_callback);
+        None
+    }
+
+    /// Gets the args that are parsed as options and values, leaving out any trailing args
+    fn parsed_args(&self) -> &[String] {
+        match self.trailing_args_index() {
+            Some(index) => &self.args[..index],
+            None => &self.args,
         }
-        self.run_callbacks(callbacks)
     }
 
-    pub fn has_a_value(&self, arg_name: String) -> bool {
-        let mut counter = 0;
-        let binding = self.get_callable_name(arg_name);
-        let arg_full_name = binding.trim();
-        for arg in &self.args {
-            if self.get_callable_name(arg.to_string()) == arg_full_name {
-                if let Some(value) = self.args.get(counter + 1) {
-                    if !value.starts_with("-") {
-                        return true;
-                    }
+    /// Gets the param type template (`""`, `[]`, `<>`, `[...]` or `<...>`) an option was registered with
+    fn get_param_type(&self, arg_name: &str) -> Option<&'static str> {
+        ["", "[]", "<>", "[...]", "<...>"]
+            .into_iter()
+            .find(|template| {
+                self.args_hash_table
+                    .contains_key(format!("{arg_name} {template}").trim())
+            })
+    }
+
+    /// Gets the names of the commands leading to this one, the app first
+    ///
+    /// # Example
+    /// ```
+    /// app.command("remote", "Manage remotes").command("add", "Add a remote").default(|x| {
+    ///     // $ app remote add   => app remote add <name> <url>
+    ///     fli_println!("usage: {} <name> <url>", x.command_path().join(" "));
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `Vec<&str>` - The path, like `["app", "remote", "add"]`
+    pub fn command_path(&self) -> Vec<&str> {
+        let mut path = match &self.parent {
+            Some(parent) => parent.command_path(),
+            None => vec![],
+        };
+        path.push(&self.name);
+        path
+    }
+
+    /// Gets the command this one was called from, with the args passed to it before this command
+    /// its options can be read like in its own callbacks, see `get_global` to read an option from any of them
+    ///
+    /// # Returns
+    /// * `Option<&Fli>` - The parent, none for the app
+    pub fn parent(&self) -> Option<&Fli> {
+        self.parent.as_deref()
+    }
+
+    /// Gets the description of the app or command
+    pub fn get_description(&self) -> String {
+        self.description.to_owned()
+    }
+
+    /// Gets the options of the app or command in the order of the help screen
+    ///
+    /// # Example
+    /// ```
+    /// for option in app.options() {
+    ///     println!("{} {}", option.long, option.description);
+    /// }
+    /// ```
+    ///
+    /// # Returns
+    /// * `Vec<OptionInfo>` - The options
+    pub fn options(&self) -> Vec<OptionInfo> {
+        self.help_keys()
+            .into_iter()
+            .filter(|key| !self.cammands_hash_tables.contains_key(*key))
+            .map(|key| {
+                let parts: Vec<&str> = key.split_whitespace().collect();
+                let long = parts.get(1).copied().unwrap_or_default();
+                let hint = self.value_hints.get(long);
+                OptionInfo {
+                    long: long.to_string(),
+                    short: parts
+                        .first()
+                        .filter(|short| **short != long)
+                        .map(|short| short.to_string()),
+                    param: parts.get(2).copied().unwrap_or_default().to_string(),
+                    description: self.help_hash_table[key].to_string(),
+                    value_hint: hint.map(ValueHint::name).unwrap_or_default().to_string(),
+                    choices: hint.map(ValueHint::values).unwrap_or_default(),
                 }
-            }
-            counter += 1;
-        }
-        return false;
+            })
+            .collect()
     }
 
-    fn run_callbacks(&self, callbacks: Vec<for<'a> fn(&'a Fli)>) -> &Self {
-        for callback in callbacks.clone() {
-            callback(self)
+    /// Gets the positional args of the app or command described with `argument`, in the order they were added
+    ///
+    /// # Returns
+    /// * `Vec<ArgumentInfo>` - The positional args
+    pub fn arguments(&self) -> Vec<ArgumentInfo> {
+        self.arguments
+            .iter()
+            .map(|(name, description)| {
+                let (name, param) = split_argument_name(name);
+                ArgumentInfo {
+                    name: name.to_string(),
+                    param,
+                    description: description.to_string(),
+                }
+            })
+            .collect()
+    }
+
+    /// Gets the commands of the app or command in the order of the help screen
+    ///
+    /// # Returns
+    /// * `Vec<&Fli>` - The commands, read their name with `get_app_name`
+    pub fn commands(&self) -> Vec<&Fli> {
+        self.help_keys()
+            .into_iter()
+            .filter_map(|key| self.cammands_hash_tables.get(key))
+            .collect()
+    }
+
+    /// Describes the app or command and all the commands under it
+    ///
+    /// # Example
+    /// ```
+    /// let spec = app.to_spec();
+    /// // with the `serde` feature
+    /// let json = serde_json::to_string_pretty(&spec)?;
+    /// ```
+    ///
+    /// # Returns
+    /// * `CliSpec` - The definition
+    pub fn to_spec(&self) -> CliSpec {
+        CliSpec {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+            version: self.version.to_string(),
+            options: self.options(),
+            arguments: self.arguments(),
+            commands: self.commands().into_iter().map(Fli::to_spec).collect(),
         }
-        self
     }
-    /**
-     * Gets the Long name for a short arg
-     */
-    pub fn get_callable_name(&self, arg: String) -> String {
-        let mut arg_template: String = String::from(format!("{}", arg));
-        if !arg_template.starts_with("-") {
-            arg_template = String::from(format!("-{}", arg));
+
+    /// Builds an app with the commands, options and positional args of a spec, the callbacks doing nothing
+    /// useful for tests or tools working from a spec, set the callbacks with `override_option` and `default`
+    /// # Arguments
+    /// * `spec` - The definition, like the one from `to_spec`
+    ///
+    /// # Example
+    /// ```
+    /// let skeleton = Fli::from_spec(&app.to_spec());
+    /// assert_eq!(skeleton.to_spec(), app.to_spec());
+    /// ```
+    ///
+    /// # Returns
+    /// * `Fli` - The app
+    pub fn from_spec(spec: &CliSpec) -> Fli {
+        let mut app = Fli::init(&spec.name, &spec.description);
+        app.set_version(&spec.version);
+        app.apply_spec(spec);
+        app
+    }
+
+    /// Adds the options, positional args and commands of a spec
+    fn apply_spec(&mut self, spec: &CliSpec) {
+        for option in &spec.options {
+            // like `--help`, added already
+            if self.get_param_type(&option.long).is_some() {
+                continue;
+            }
+            let mut key = match &option.short {
+                Some(short) => format!("{short} {}", option.long),
+                None => option.long.to_string(),
+            };
+            if !option.param.is_empty() {
+                key = format!("{key}, {}", option.param);
+            }
+            self.option(&key, &option.description, |_x| {});
+            if let Some(hint) = ValueHint::from_name(&option.value_hint, &option.choices) {
+                self.value_hint(&option.long, hint);
+            }
         }
-        if let Some(long_name) = self.short_hash_table.get(&arg_template) {
-            arg_template = long_name.to_string();
+        for argument in &spec.arguments {
+            let (open, close) = match argument.param.starts_with('[') {
+                true => ("[", "]"),
+                false => ("<", ">"),
+            };
+            let multiple = if argument.param.contains("...") { "..." } else { "" };
+            let name = format!("{open}{}{close}{multiple}", argument.name);
+            self.argument(&name, &argument.description);
         }
-        if !arg_template.starts_with("--") {
-            arg_template = String::from(format!("--{}", arg));
+        for command in &spec.commands {
+            self.command(&command.name, &command.description)
+                .apply_spec(command);
         }
-        return arg_template;
     }
-    pub fn get_values(&self, arg: String) -> Result<Vec<String>, &str> {
-        let mut values: Vec<String> = vec![];
-        let arg_name: String = self.get_callable_name(arg);
-        // if the argument does not need a param then dont return none
-        if let Some(_) = self.args_hash_table.get(&arg_name) {
-            return Err("Does not expect a value");
+
+    /// Visits the app or command then all the commands under it, parents before their commands,
+    /// for tools generating docs or completions or checking the definition of the app
+    /// # Arguments
+    /// * `visitor` - The function called with each command and its depth, 0 for the one `walk` is called on
+    ///
+    /// # Example
+    /// ```
+    /// app.walk(|command, depth| {
+    ///     println!("{}{} : {} options", "  ".repeat(depth), command.get_app_name(), command.options().len());
+    /// });
+    /// ```
+    pub fn walk(&self, mut visitor: impl FnMut(&Fli, usize)) {
+        self.walk_from(&mut visitor, 0);
+    }
+
+    fn walk_from(&self, visitor: &mut impl FnMut(&Fli, usize), depth: usize) {
+        visitor(self, depth);
+        for command in self.commands() {
+            command.walk_from(visitor, depth + 1);
         }
-        let mut counter = 1;
-        for mut i in self.args.clone() {
-            i = self.get_callable_name(i);
-            if i != arg_name {
-                counter += 1;
+    }
+
+    /// Gets the values of an option of this command or, when not passed to it, of the commands it was called from
+    /// like `app -n james greet` where `--name` is an option of `app` read in `greet`
+    /// # Arguments
+    /// * `arg` - The option to get the values for (`-n`, `--name`, `n` or `name`)
+    ///
+    /// # Example
+    /// ```
+    /// app.option("-n --name, <>", "Your name", |x| {});
+    /// app.command("greet", "Greet someone").default(|x| {
+    ///     if let Ok(name) = x.get_global("name".to_string()) {
+    ///         fli_println!("Hello {}", name[0]);
+    ///     }
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `Result<Vec<String>, &str>` - The values or an error message if no command was passed a value for it
+    pub fn get_global(&self, arg: String) -> Result<Vec<String>, &str> {
+        let values = self.get_values(arg.clone());
+        match &self.parent {
+            Some(parent) if values.is_err() => parent.get_global(arg),
+            _ => values,
+        }
+    }
+
+    /// Checks if an option is passed to this command or to the commands it was called from, see `get_global`
+    /// # Arguments
+    /// * `param` - The option to check (`-v`, `--verbose`, `v` or `verbose`)
+    ///
+    /// # Returns
+    /// * `bool` - Whether it is passed
+    pub fn is_passed_global(&self, param: String) -> bool {
+        self.is_passed(param.clone())
+            || self
+                .parent
+                .as_ref()
+                .is_some_and(|parent| parent.is_passed_global(param))
+    }
+
+    /// Gets the options passed on the command line with their values, in the order they were first passed
+    /// the options of the commands this one was called from come first, so a command can forward or log exactly what was specified
+    ///
+    /// # Example
+    /// ```
+    /// app.command("deploy", "Deploy the app").after(|x| {
+    ///     // $ app -v deploy --env prod   => [("--verbose", []), ("--env", ["prod"])]
+    ///     for (option, values) in x.provided_options() {
+    ///         fli_println!("{option} {}", values.join(" "));
+    ///     }
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `Vec<(String, Vec<String>)>` - The long name of each option and its values, empty for flags
+    pub fn provided_options(&self) -> Vec<(String, Vec<String>)> {
+        let mut provided = match &self.parent {
+            Some(parent) => parent.provided_options(),
+            None => vec![],
+        };
+        let mut seen: Vec<String> = vec![];
+        for arg in self.parsed_args().iter().skip(1) {
+            if !self.is_option_token(arg) {
                 continue;
             }
-            let binding = &String::from(format!("{} []", arg_name));
-            if let Some(_) = self.args_hash_table.get(binding) {
-                if let Some(v) = self.args.get(counter) {
-                    if v.starts_with("-") {
-                        return Err("No value passed");
-                    }
-                    values.push(v.to_string());
-                    break;
-                }
+            let long = self.get_callable_name(arg.to_string());
+            if self.get_param_type(&long).is_none() || seen.contains(&long) {
+                continue;
             }
-            let binding = &String::from(format!("{} <>", arg_name));
-            if let Some(_) = self.args_hash_table.get(binding) {
-                if let Some(v) = self.args.get(counter) {
-                    if v.starts_with("-") {
-                        return Err("No value Passed");
+            let values = self.get_values(long.to_string()).unwrap_or_default();
+            seen.push(long.to_string());
+            provided.push((long, values));
+        }
+        provided
+    }
+
+    /// Rebuilds the command line this command was called with, normalized and quoted so a shell reads it back the same
+    /// options use their long names, the commands are named from the app down and trailing args come after `--`,
+    /// handy for logs and "re-run with" hints
+    ///
+    /// # Example
+    /// ```
+    /// app.command("deploy", "Deploy the app").after(|x| {
+    ///     // $ app -v deploy -e prod 'my app'   => app --verbose deploy --env prod 'my app'
+    ///     fli_println!("re-run with: {}", x.to_command_line());
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// * `String` - The command line, its words split by spaces
+    pub fn to_command_line(&self) -> String {
+        self.command_line_words()
+            .iter()
+            .map(|word| quote_shell_word(word))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    /// Gets the words of the normalized command line, see `to_command_line`
+    fn command_line_words(&self) -> Vec<String> {
+        let mut words = match &self.parent {
+            Some(parent) => parent.command_line_words(),
+            None => vec![],
+        };
+        words.push(self.name.to_string());
+        let args = self.parsed_args();
+        let mut index = 1;
+        while index < args.len() {
+            let arg = &args[index];
+            index += 1;
+            let long = self.get_callable_name(arg.to_string());
+            let param_type = match self.get_param_type(&long) {
+                Some(param_type) if self.is_option_token(arg) => param_type,
+                // positional args and unknown options are kept as passed
+                _ => {
+                    if arg != "--" {
+                        words.push(arg.to_string());
                     }
-                    values.push(v.to_string());
-                    break;
+                    continue;
                 }
-            }
-            let binding = &String::from(format!("{} [...]", arg_name));
-            if let Some(_) = self.args_hash_table.get(binding) {
-                if let Some(params) = self.args.get((counter)..self.args.len()) {
-                    for i in params {
-                        if i.starts_with(&"-".to_string()) {
-                            break;
-                        }
-                        values.push(i.to_string());
-                    }
+            };
+            words.push(long);
+            while !param_type.is_empty()
+                && index < args.len()
+                && !self.ends_values(&args[index], param_type)
+            {
+                words.push(args[index].to_string());
+                index += 1;
+                if !param_type.contains("...") {
+                    break;
                 }
             }
-            let binding = &String::from(format!("{} <...>", arg_name));
-            if let Some(_) = self.args_hash_table.get(binding) {
-                if let Some(params) = self.args.get((counter)..self.args.len()) {
-                    for i in params {
-                        if i.starts_with(&"-".to_string()) {
-                            break;
-                        }
-                        values.push(i.to_string());
-                    }
-                }
+            if args.get(index).is_some_and(|arg| self.is_value_terminator(arg, param_type)) {
+                words.push(args[index].to_string());
+                index += 1;
             }
-            counter += 1;
         }
-        if values.len() > 0 {
-            return Ok(values);
+        let trailing = self.trailing_args();
+        if !trailing.is_empty() {
+            words.push("--".to_string());
+            words.extend(trailing);
         }
-        return Err("No value passed");
+        words
     }
+
     pub fn is_passed(&self, param: String) -> bool {
-        for i in self.args.clone() {
-            if self.get_callable_name(i) == self.get_callable_name(param.clone()) {
-                return true;
-            }
-        }
-        return false;
+        let arg_name = self.get_callable_name(param);
+        self.is_passed_in_args(&arg_name) || self.is_set_in_config(&arg_name)
+    }
+
+    /// Checks if an option is passed on the command line, leaving out the config file
+    fn is_passed_in_args(&self, arg_name: &str) -> bool {
+        self.parsed_args()
+            .iter()
+            .any(|arg| self.get_callable_name(arg.to_string()) == arg_name)
     }
     pub fn get_arg_at(&self, index: u8) -> Option<String> {
         if let Some(arg) = self.args.get(index as usize) {
             return Some(arg.to_string());
         }
-        return None;
+        None
+    }
+}
+
+/// Gets the table of a group, adding it at the end when it is new
+fn group_table<'a, 'b>(
+    groups: &'b mut Vec<(Option<&'a str>, Table)>,
+    group: Option<&'a str>,
+    new_table: impl Fn() -> Table,
+) -> &'b mut Table {
+    let index = match groups.iter().position(|(name, _)| *name == group) {
+        Some(index) => index,
+        None => {
+            groups.push((group, new_table()));
+            groups.len() - 1
+        }
+    };
+    &mut groups[index].1
+}
+
+/// The callback of the help option, printing the short help screen for `-h` or the long one for `--help` and stopping
+fn help_callback(app: &Fli) {
+    if !app.long_help_requested() {
+        return app.default_help();
+    }
+    app.show_help(&app.long_help_screen());
+    app.request_exit(0);
+}
+
+/// The callback of the `help` command, printing the help screen of the command at the path passed to it and stopping
+fn help_command_callback(app: &Fli) {
+    let Some(parent) = app.parent() else {
+        return app.default_help();
+    };
+    let path = app.positional_args();
+    let mut command = parent;
+    for name in &path {
+        let Some(next) = command.find_command(name) else {
+            let error = display::theme().error;
+            let message = fill(&messages::messages().command_not_found, &[("command", &name.bold().color(error).to_string())]);
+            fli_println!("{message}");
+            command.print_did_you_mean(&command.get_most_similar_commands(name));
+            return app.request_exit(1);
+        };
+        command = next;
+    }
+    if let Some(help) = parent.render_help_for(&path.join(" ")) {
+        app.show_help(&help);
+    }
+    app.request_exit(0);
+}
+
+/// The callback of the version option, printing `name version` and stopping
+fn version_callback(app: &Fli) {
+    fli_println!("{} {}", app.name, app.version);
+    app.request_exit(0);
+}
+
+/// Builds the key of `Fli::option` from the names of an option and its param type template
+fn option_key(short: &str, long: &str, template: &str) -> String {
+    let names = match short {
+        "" => long.to_string(),
+        short => format!("{short} {long}"),
+    };
+    match template {
+        "" => names,
+        template => format!("{names}, {template}"),
+    }
+}
+
+/// Gets how a param type template (`<>`, `[]`, `<...>` or `[...]`) is shown in the help screen
+fn param_type_name(template: &str, messages: &Messages) -> String {
+    match template {
+        "<>" => messages.required.clone(),
+        "[]" => messages.optional.clone(),
+        "<...>" => messages.required_multiple.clone(),
+        "[...]" => messages.optional_multiple.clone(),
+        _ => messages.no_value.clone(),
+    }
+}
+
+/// Splits the name template of a positional arg like `<files>...` into its name and param type template like `<...>`
+/// names without brackets are required
+fn split_argument_name(name: &str) -> (&str, String) {
+    let (name, multiple) = match name.strip_suffix("...") {
+        Some(name) => (name, "..."),
+        None => (name, ""),
+    };
+    if let Some(name) = name.strip_prefix('[').and_then(|name| name.strip_suffix(']')) {
+        return (name, format!("[{multiple}]"));
     }
+    let name = name.strip_prefix('<').and_then(|name| name.strip_suffix('>')).unwrap_or(name);
+    (name, format!("<{multiple}>"))
 }
@@ -1,14 +1,81 @@
 
 #[cfg(not(doctest))]
 pub mod fli;
+#[cfg(feature = "clap")]
+pub mod compat;
+pub mod completions;
+mod config;
+pub mod display;
+pub mod error;
 pub mod macros;
+pub mod messages;
+pub mod paths;
+pub mod prompt;
+#[cfg(feature = "readline")]
+mod readline;
+pub mod testing;
+pub mod value;
 
-pub use fli::Fli;
+pub use error::{FliError, FliErrorKind};
+pub use fli::{
+    Action, ArgAction, ArgumentInfo, CliSpec, DefaultErrorRenderer, DefaultHelpRenderer, ErrorRenderer, Fli, HelpOrder, HelpRenderer,
+    OptionInfo, UnknownOptionPolicy, Validator, ValueHint, HELP_PRIORITY, VERSION_PRIORITY,
+};
+pub use value::{FliValue, Input};
 use colored::Colorize;
+use std::{
+    cell::Cell,
+    panic::{self, AssertUnwindSafe},
+    process,
+    sync::Once,
+    time::Duration,
+};
 #[cfg(test)]
 pub mod tests;
 
 
+thread_local! {
+    /// Whether `exit` should unwind back to `catch_exit` instead of ending the process
+    static CATCH_EXIT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The payload `exit` unwinds with while inside `catch_exit`
+struct ExitRequest(i32);
+
+/// Exits the process with the given code, like `std::process::exit`
+///
+/// fli uses it for the help screen and other exits, use it in your callbacks too
+/// so `fli::testing::TestApp` can run them without ending the test process
+pub fn exit(code: i32) -> ! {
+    if CATCH_EXIT.with(Cell::get) {
+        panic::panic_any(ExitRequest(code));
+    }
+    process::exit(code)
+}
+
+/// Runs `f`, returning the exit code instead of ending the process if it calls `exit`
+fn catch_exit<T>(f: impl FnOnce() -> T) -> Result<T, i32> {
+    static SILENCE_EXIT_PANICS: Once = Once::new();
+    SILENCE_EXIT_PANICS.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !info.payload().is::<ExitRequest>() {
+                default_hook(info);
+            }
+        }));
+    });
+    let was_catching = CATCH_EXIT.with(|c| c.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCH_EXIT.with(|c| c.set(was_catching));
+    match result {
+        Ok(value) => Ok(value),
+        Err(payload) => match payload.downcast::<ExitRequest>() {
+            Ok(request) => Err(request.0),
+            Err(payload) => panic::resume_unwind(payload),
+        },
+    }
+}
+
 pub fn add(left: usize, right: usize) -> usize {
     left + right
 }
@@ -26,11 +93,11 @@ fn levenshtein_distance(s1: &str, s2: &str) -> usize {
 
     let mut dp = vec![vec![0; n + 1]; m + 1];
 
-    for i in 0..=m {
-        dp[i][0] = i;
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
     }
-    for j in 0..=n {
-        dp[0][j] = j;
+    for (j, cell) in dp[0].iter_mut().enumerate() {
+        *cell = j;
     }
 
     for i in 1..=m {
@@ -50,22 +117,196 @@ fn levenshtein_distance(s1: &str, s2: &str) -> usize {
     dp[m][n]
 }
 
+/// Checks if a token is a negative numeric literal like `-5` or `-1.5`
+fn is_negative_number(token: &str) -> bool {
+    token.len() > 1 && token.starts_with('-') && token[1..].parse::<f64>().is_ok_and(f64::is_finite)
+}
+
+/// Parses a human duration like `30s`, `5m`, `2h30m` or `1500ms`, a bare number is taken as seconds
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let mut total = Duration::ZERO;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        if digits == 0 {
+            return None;
+        }
+        let amount: u64 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+        let unit_len = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let unit = match &rest[..unit_len] {
+            "ms" => Duration::from_millis(amount),
+            "s" => Duration::from_secs(amount),
+            "m" => Duration::from_secs(amount.checked_mul(60)?),
+            "h" => Duration::from_secs(amount.checked_mul(60 * 60)?),
+            "d" => Duration::from_secs(amount.checked_mul(60 * 60 * 24)?),
+            _ => return None,
+        };
+        total = total.checked_add(unit)?;
+        rest = &rest[unit_len..];
+    }
+    Some(total)
+}
+
+/// Parses a human byte size like `512`, `4K`, `10MB` or `1.5MiB`
+/// bare and `iB` suffixes are powers of 1024 while `B` suffixes (`KB`, `MB`, ...) are powers of 1000
+fn parse_size(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kib" => 1 << 10,
+        "m" | "mib" => 1 << 20,
+        "g" | "gib" => 1 << 30,
+        "t" | "tib" => 1 << 40,
+        "kb" => 1_000,
+        "mb" => 1_000_000,
+        "gb" => 1_000_000_000,
+        "tb" => 1_000_000_000_000,
+        _ => return None,
+    };
+    let size = number * multiplier as f64;
+    if !size.is_finite() || size > u64::MAX as f64 {
+        return None;
+    }
+    Some(size.round() as u64)
+}
+
+/// Splits a line into words like a shell, `'...'` keeps everything as is while `"..."` and `\\` escape spaces
+fn split_shell_words(line: &str) -> Result<Vec<String>, String> {
+    let mut words: Vec<String> = vec![];
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err("Missing closing quote '".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err("Missing closing quote \"".to_string()),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err("Missing closing quote \"".to_string()),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                if let Some(c) = chars.next() {
+                    word.push(c);
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Gets the line of the history a `!!` (the last one) or `!3` (the third one) line recalls, none for other lines
+fn recall_history_line(history: &[String], line: &str) -> Option<Result<String, String>> {
+    let number = match line.strip_prefix('!')? {
+        "!" => history.len(),
+        number if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) => number.parse().unwrap_or(0),
+        _ => return None,
+    };
+    Some(match number.checked_sub(1).and_then(|index| history.get(index)) {
+        Some(line) => Ok(line.to_string()),
+        None => Err(messages::fill(&messages::messages().no_history_line, &[("number", &number.to_string())])),
+    })
+}
+
+/// Quotes a word so `split_shell_words` and shells read it back as one word, words without special characters are left as is
+fn quote_shell_word(word: &str) -> String {
+    let plain = |c: char| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c);
+    if !word.is_empty() && word.chars().all(plain) {
+        return word.to_string();
+    }
+    format!("'{}'", word.replace('\'', "'\\''"))
+}
+
+/// How deep response files can include other response files
+const RESPONSE_FILE_DEPTH: u8 = 10;
+
+/// Reads the args in a response file, split like a shell would, expanding the `@file` args in it too
+fn read_response_file(path: &str, depth: u8) -> Result<Vec<String>, String> {
+    if depth >= RESPONSE_FILE_DEPTH {
+        return Err(format!(
+            "{path} : response files can not be nested more than {RESPONSE_FILE_DEPTH} deep"
+        ));
+    }
+    let content = std::fs::read_to_string(path)
+        .map_err(|error| format!("can not read {path} : {error}"))?;
+    let mut args = vec![];
+    for arg in split_shell_words(&content).map_err(|message| format!("{path} : {message}"))? {
+        match arg.strip_prefix('@') {
+            Some(path) if !path.is_empty() => args.extend(read_response_file(path, depth + 1)?),
+            _ => args.push(arg),
+        }
+    }
+    Ok(args)
+}
+
 fn fli_default_callback(x: &Fli) {
     let command: Option<String> = x.get_arg_at(1);
     let command = match command {
         Some(c) => c,
         None => "".to_string(),
     };
-    println!("Command not found: {}", command.bold().red());
-    let err_msg_prefix = match command.len() {
-        0 => "No",
-        _ => "Invalid",
-    };
+    let error = display::theme().error;
+    let messages = messages::messages();
+    fli_println!(
+        "{}",
+        messages::fill(&messages.command_not_found, &[("command", &command.bold().color(error).to_string())])
+    );
     //  if command is not empty print similar command
-    if command.len() <= 0 {
-        x.print_help("No command provided");
+    if command.is_empty() {
+        x.print_help_and_stop(&messages.no_command);
         return;
     }
-    println!("{0} Command {1} , use the '-h' or '--help' flag to see all command", err_msg_prefix, x.get_app_name().bold().red());
+    fli_println!(
+        "{}",
+        messages::fill(&messages.invalid_command, &[("name", &x.get_app_name().bold().color(error).to_string())])
+    );
     x.print_most_similar_commands(command.as_str());
 }
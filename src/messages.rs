@@ -0,0 +1,430 @@
+use std::cell::RefCell;
+
+thread_local! {
+    /// The text of fli output
+    static MESSAGES: RefCell<Messages> = RefCell::new(Messages::default());
+}
+
+/// The text fli prints: help screen headers, errors, warnings and prompts,
+/// set with `set_messages` or `Fli::set_messages` to ship a translated app
+///
+/// the messages are templates, the `{name}` like placeholders listed on each field are replaced when printed
+///
+/// # Example
+/// ```
+/// use fli::messages::{messages, set_messages, Messages};
+///
+/// set_messages(Messages {
+///     options_header: "Flags".to_string(),
+///     unknown_option: "No such flag {option}".to_string(),
+///     ..Messages::default()
+/// });
+/// assert_eq!(messages().options_header, "Flags");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Messages {
+    /// The label of the app name in the help screen
+    pub name_label: String,
+    /// The label of the version in the help screen
+    pub version_label: String,
+    /// The label of the description in the help screen
+    pub description_label: String,
+    /// The label of the usage line in the help screen
+    pub usage_label: String,
+    /// The header of the positional args in the help screen
+    pub arguments_header: String,
+    /// The header of the options without a group in the help screen
+    pub options_header: String,
+    /// The header of the commands without a category in the help screen
+    pub commands_header: String,
+    /// The column of option long names in the help screen
+    pub long_column: String,
+    /// The column of option short names in the help screen
+    pub short_column: String,
+    /// The column of command and positional arg names in the help screen
+    pub name_column: String,
+    /// The column of param types in the help screen
+    pub param_type_column: String,
+    /// The column of descriptions in the help screen
+    pub description_column: String,
+    /// The param type of `<>` options
+    pub required: String,
+    /// The param type of `[]` options
+    pub optional: String,
+    /// The param type of `<...>` options
+    pub required_multiple: String,
+    /// The param type of `[...]` options
+    pub optional_multiple: String,
+    /// The param type of options without a value
+    pub no_value: String,
+    /// The description of the help option, `{name}` is the app or command
+    pub help_option: String,
+    /// The description of the version option, `{name}` is the app or command
+    pub version_option: String,
+    /// The description of the `help` command, `{name}` is the app or command it is under
+    pub help_command: String,
+    /// After the description of options with choices in the long help screen, `{values}` are the choices
+    pub possible_values: String,
+    /// When `!{number}` in `Fli::repl` is not a line of the history
+    pub no_history_line: String,
+    /// The description of the `--color` option
+    pub color_option: String,
+    /// The description of the `--verbose` option
+    pub verbose_option: String,
+    /// The description of the `--quiet` option
+    pub quiet_option: String,
+    /// The description of the `--config` option, `{path}` is the default config file
+    pub config_option: String,
+    /// The title of the error banner
+    pub error_banner: String,
+    /// An error in a command rather than the app, `{command}` is the command path like `app remote add` and `{message}` the error
+    pub error_in_command: String,
+    /// Before the suggestions for unknown options and commands
+    pub did_you_mean: String,
+    /// When the command passed does not exist, `{command}` is the command
+    pub command_not_found: String,
+    /// What to do when the command passed does not exist, `{name}` is the app
+    pub invalid_command: String,
+    /// The error when no command was passed to an app that needs one
+    pub no_command: String,
+    /// The label of warnings
+    pub warning_label: String,
+    /// When a deprecated option or command is used, `{name}` is the option or command and `{hint}` what to use instead
+    pub deprecated: String,
+    /// When an unknown option is ignored, `{option}` is the option
+    pub ignoring_unknown_option: String,
+    /// `FliError::MissingValue`, `{option}` is the option
+    pub missing_value: String,
+    /// `FliError::InvalidValue`, `{option}` is the option and `{message}` why the value is not valid
+    pub invalid_value: String,
+    /// `FliError::UnknownOption`, `{option}` is the option
+    pub unknown_option: String,
+    /// `FliError::MissingSubcommand`, `{command}` is the command
+    pub missing_subcommand: String,
+    /// `FliError::AmbiguousOption`, `{option}` is the option and `{candidates}` the options it could be
+    pub ambiguous_option: String,
+    /// `FliError::MissingArguments`, with the `{command}`, `{expected}`, `{got}` and `{usage}` placeholders
+    pub missing_arguments: String,
+    /// `FliError::TooManyArguments`, with the `{argument}`, `{expected}` and `{got}` placeholders
+    pub too_many_arguments: String,
+    /// `FliError::ValidationFailed`, `{message}` is why the args are not valid
+    pub validation_failed: String,
+    /// `FliError::ExclusiveOption`, `{option}` is the exclusive option and `{other}` the arg passed with it
+    pub exclusive_option: String,
+    /// Why the values of an option are not valid when there are fewer than `Fli::value_count` allows, with `{min}` and `{got}`
+    pub too_few_values: String,
+    /// Why the values of an option are not valid when there are more than `Fli::value_count` allows, with `{max}` and `{got}`
+    pub too_many_values: String,
+    /// Why the values of an option are not valid when `Fli::value_count` asks for an exact count, with `{count}` and `{got}`
+    pub wrong_value_count: String,
+    /// The answer hint of `prompt::confirm` when the default is yes
+    pub confirm_default_yes: String,
+    /// The answer hint of `prompt::confirm` when the default is no
+    pub confirm_default_no: String,
+    /// The word for yes accepted by `prompt::confirm` along with its first letter, `yes` is always accepted
+    pub yes: String,
+    /// The word for no accepted by `prompt::confirm` along with its first letter, `no` is always accepted
+    pub no: String,
+    /// When `prompt::confirm` gets something else than yes or no
+    pub answer_yes_or_no: String,
+    /// When a prompt is asked with input turned off, `{question}` is the question
+    pub answer_required: String,
+    /// When a prompt has nothing to read, `{question}` is the question
+    pub no_input: String,
+    /// When `prompt::select` is given no items, `{question}` is the question
+    pub nothing_to_select: String,
+    /// The prompt of `prompt::select`, `{count}` is the number of items
+    pub select: String,
+    /// When `prompt::select` gets a number out of the items, `{answer}` is the answer
+    pub not_a_choice: String,
+}
+
+impl Messages {
+    /// The English messages, the default
+    pub fn english() -> Self {
+        Self {
+            name_label: "Name".to_string(),
+            version_label: "Version".to_string(),
+            description_label: "Description".to_string(),
+            usage_label: "Usage".to_string(),
+            arguments_header: "Arguments".to_string(),
+            options_header: "Options".to_string(),
+            commands_header: "Commands".to_string(),
+            long_column: "Long".to_string(),
+            short_column: "Short".to_string(),
+            name_column: "Name".to_string(),
+            param_type_column: "ParamType".to_string(),
+            description_column: "Description".to_string(),
+            required: "Required".to_string(),
+            optional: "Optional".to_string(),
+            required_multiple: "Required Multiple".to_string(),
+            optional_multiple: "Optional Multiple".to_string(),
+            no_value: "None".to_string(),
+            help_option: "print help screen for {name}".to_string(),
+            version_option: "print version for {name}".to_string(),
+            help_command: "print help for {name} or one of its commands".to_string(),
+            possible_values: "[possible values: {values}]".to_string(),
+            no_history_line: "No line {number} in the history".to_string(),
+            color_option: "when to color output: auto, always or never".to_string(),
+            verbose_option: "print more output, repeat for even more".to_string(),
+            quiet_option: "print less output, repeat for even less".to_string(),
+            config_option: "read option values from this file, {path} by default".to_string(),
+            error_banner: "ERROR".to_string(),
+            error_in_command: "error in '{command}': {message}".to_string(),
+            did_you_mean: "Did you mean:".to_string(),
+            command_not_found: "Command not found: {command}".to_string(),
+            invalid_command:
+                "Invalid Command {name} , use the '-h' or '--help' flag to see all command"
+                    .to_string(),
+            no_command: "No command provided".to_string(),
+            warning_label: "Warning:".to_string(),
+            deprecated: "{name} is deprecated, {hint}".to_string(),
+            ignoring_unknown_option: "ignoring unknown option {option}".to_string(),
+            missing_value: "Invalid syntax : {option} does not have a value".to_string(),
+            invalid_value: "Invalid value for {option} : {message}".to_string(),
+            unknown_option: "Unknown option {option}".to_string(),
+            missing_subcommand: "{command} requires a command".to_string(),
+            ambiguous_option: "Ambiguous option {option} : could be {candidates}".to_string(),
+            missing_arguments:
+                "{command} expects at least {expected} arguments, got {got} (usage: {usage})"
+                    .to_string(),
+            too_many_arguments:
+                "Unexpected argument {argument} : expected at most {expected}, got {got}"
+                    .to_string(),
+            validation_failed: "Invalid arguments : {message}".to_string(),
+            exclusive_option: "{option} can not be used with {other}".to_string(),
+            too_few_values: "expected at least {min} values, got {got}".to_string(),
+            too_many_values: "expected at most {max} values, got {got}".to_string(),
+            wrong_value_count: "expected {count} values, got {got}".to_string(),
+            confirm_default_yes: "[Y/n]".to_string(),
+            confirm_default_no: "[y/N]".to_string(),
+            yes: "yes".to_string(),
+            no: "no".to_string(),
+            answer_yes_or_no: "Please answer yes or no".to_string(),
+            answer_required: "{question} : an answer is required".to_string(),
+            no_input: "{question} : no input".to_string(),
+            nothing_to_select: "{question} : nothing to select".to_string(),
+            select: "Select 1-{count}: ".to_string(),
+            not_a_choice: "'{answer}' is not one of the numbers".to_string(),
+        }
+    }
+
+    /// The French messages
+    pub fn french() -> Self {
+        Self {
+            name_label: "Nom".to_string(),
+            version_label: "Version".to_string(),
+            description_label: "Description".to_string(),
+            usage_label: "Utilisation".to_string(),
+            arguments_header: "Arguments".to_string(),
+            options_header: "Options".to_string(),
+            commands_header: "Commandes".to_string(),
+            long_column: "Long".to_string(),
+            short_column: "Court".to_string(),
+            name_column: "Nom".to_string(),
+            param_type_column: "Paramètre".to_string(),
+            description_column: "Description".to_string(),
+            required: "Requis".to_string(),
+            optional: "Facultatif".to_string(),
+            required_multiple: "Requis Multiple".to_string(),
+            optional_multiple: "Facultatif Multiple".to_string(),
+            no_value: "Aucun".to_string(),
+            help_option: "affiche l'aide de {name}".to_string(),
+            version_option: "affiche la version de {name}".to_string(),
+            help_command: "affiche l'aide de {name} ou d'une de ses commandes".to_string(),
+            possible_values: "[valeurs possibles : {values}]".to_string(),
+            no_history_line: "Aucune ligne {number} dans l'historique".to_string(),
+            color_option: "quand colorer la sortie : auto, always ou never".to_string(),
+            verbose_option: "affiche plus de détails, à répéter pour en avoir encore plus".to_string(),
+            quiet_option: "affiche moins de détails, à répéter pour en avoir encore moins".to_string(),
+            config_option: "lit les valeurs des options depuis ce fichier, {path} par défaut".to_string(),
+            error_banner: "ERREUR".to_string(),
+            error_in_command: "erreur dans '{command}' : {message}".to_string(),
+            did_you_mean: "Vouliez-vous dire :".to_string(),
+            command_not_found: "Commande introuvable : {command}".to_string(),
+            invalid_command: "Commande {name} invalide, utilisez l'option '-h' ou '--help' pour voir toutes les commandes"
+                .to_string(),
+            no_command: "Aucune commande fournie".to_string(),
+            warning_label: "Attention :".to_string(),
+            deprecated: "{name} est obsolète, {hint}".to_string(),
+            ignoring_unknown_option: "option inconnue {option} ignorée".to_string(),
+            missing_value: "Syntaxe invalide : {option} n'a pas de valeur".to_string(),
+            invalid_value: "Valeur invalide pour {option} : {message}".to_string(),
+            unknown_option: "Option inconnue {option}".to_string(),
+            missing_subcommand: "{command} a besoin d'une commande".to_string(),
+            ambiguous_option: "Option ambiguë {option} : peut être {candidates}".to_string(),
+            missing_arguments: "{command} attend au moins {expected} arguments, {got} reçus (utilisation : {usage})"
+                .to_string(),
+            too_many_arguments: "Argument inattendu {argument} : au plus {expected} attendus, {got} reçus"
+                .to_string(),
+            validation_failed: "Arguments invalides : {message}".to_string(),
+            exclusive_option: "{option} ne peut pas être utilisée avec {other}".to_string(),
+            too_few_values: "au moins {min} valeurs attendues, {got} reçues".to_string(),
+            too_many_values: "au plus {max} valeurs attendues, {got} reçues".to_string(),
+            wrong_value_count: "{count} valeurs attendues, {got} reçues".to_string(),
+            confirm_default_yes: "[O/n]".to_string(),
+            confirm_default_no: "[o/N]".to_string(),
+            yes: "oui".to_string(),
+            no: "non".to_string(),
+            answer_yes_or_no: "Veuillez répondre oui ou non".to_string(),
+            answer_required: "{question} : une réponse est requise".to_string(),
+            no_input: "{question} : aucune saisie".to_string(),
+            nothing_to_select: "{question} : rien à choisir".to_string(),
+            select: "Choisissez 1-{count} : ".to_string(),
+            not_a_choice: "'{answer}' n'est pas un des numéros".to_string(),
+        }
+    }
+
+    /// The Spanish messages
+    pub fn spanish() -> Self {
+        Self {
+            name_label: "Nombre".to_string(),
+            version_label: "Versión".to_string(),
+            description_label: "Descripción".to_string(),
+            usage_label: "Uso".to_string(),
+            arguments_header: "Argumentos".to_string(),
+            options_header: "Opciones".to_string(),
+            commands_header: "Comandos".to_string(),
+            long_column: "Largo".to_string(),
+            short_column: "Corto".to_string(),
+            name_column: "Nombre".to_string(),
+            param_type_column: "Parámetro".to_string(),
+            description_column: "Descripción".to_string(),
+            required: "Obligatorio".to_string(),
+            optional: "Opcional".to_string(),
+            required_multiple: "Obligatorio Múltiple".to_string(),
+            optional_multiple: "Opcional Múltiple".to_string(),
+            no_value: "Ninguno".to_string(),
+            help_option: "muestra la ayuda de {name}".to_string(),
+            version_option: "muestra la versión de {name}".to_string(),
+            help_command: "muestra la ayuda de {name} o de uno de sus comandos".to_string(),
+            possible_values: "[valores posibles: {values}]".to_string(),
+            no_history_line: "No hay ninguna línea {number} en el historial".to_string(),
+            color_option: "cuándo colorear la salida: auto, always o never".to_string(),
+            verbose_option: "muestra más detalles, repítela para ver aún más".to_string(),
+            quiet_option: "muestra menos detalles, repítela para ver aún menos".to_string(),
+            config_option: "lee los valores de las opciones de este archivo, {path} por defecto".to_string(),
+            error_banner: "ERROR".to_string(),
+            error_in_command: "error en '{command}': {message}".to_string(),
+            did_you_mean: "¿Quisiste decir?".to_string(),
+            command_not_found: "Comando no encontrado: {command}".to_string(),
+            invalid_command: "Comando {name} inválido, usa la opción '-h' o '--help' para ver todos los comandos"
+                .to_string(),
+            no_command: "No se indicó ningún comando".to_string(),
+            warning_label: "Aviso:".to_string(),
+            deprecated: "{name} está obsoleto, {hint}".to_string(),
+            ignoring_unknown_option: "se ignora la opción desconocida {option}".to_string(),
+            missing_value: "Sintaxis inválida: {option} no tiene valor".to_string(),
+            invalid_value: "Valor inválido para {option}: {message}".to_string(),
+            unknown_option: "Opción desconocida {option}".to_string(),
+            missing_subcommand: "{command} necesita un comando".to_string(),
+            ambiguous_option: "Opción ambigua {option}: puede ser {candidates}".to_string(),
+            missing_arguments: "{command} espera al menos {expected} argumentos, recibió {got} (uso: {usage})"
+                .to_string(),
+            too_many_arguments: "Argumento inesperado {argument}: se esperaban como máximo {expected}, se recibieron {got}"
+                .to_string(),
+            validation_failed: "Argumentos inválidos: {message}".to_string(),
+            exclusive_option: "{option} no se puede usar con {other}".to_string(),
+            too_few_values: "se esperaban al menos {min} valores, se recibieron {got}".to_string(),
+            too_many_values: "se esperaban como máximo {max} valores, se recibieron {got}".to_string(),
+            wrong_value_count: "se esperaban {count} valores, se recibieron {got}".to_string(),
+            confirm_default_yes: "[S/n]".to_string(),
+            confirm_default_no: "[s/N]".to_string(),
+            yes: "sí".to_string(),
+            no: "no".to_string(),
+            answer_yes_or_no: "Por favor responde sí o no".to_string(),
+            answer_required: "{question}: se necesita una respuesta".to_string(),
+            no_input: "{question}: no hay entrada".to_string(),
+            nothing_to_select: "{question}: nada que elegir".to_string(),
+            select: "Elige 1-{count}: ".to_string(),
+            not_a_choice: "'{answer}' no es uno de los números".to_string(),
+        }
+    }
+
+    /// Gets the built-in messages of a locale, English when there are none for it
+    /// # Arguments
+    /// * `locale` - The locale like `fr`, `es_MX` or `fr_FR.UTF-8`, only the language is looked at
+    ///
+    /// # Example
+    /// ```
+    /// use fli::messages::Messages;
+    ///
+    /// assert_eq!(Messages::for_locale("fr_FR.UTF-8"), Messages::french());
+    /// assert_eq!(Messages::for_locale("ja"), Messages::english());
+    /// ```
+    ///
+    /// # Returns
+    /// * `Messages` - The messages
+    pub fn for_locale(locale: &str) -> Self {
+        let language = locale
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default();
+        match language.to_ascii_lowercase().as_str() {
+            "fr" => Self::french(),
+            "es" => Self::spanish(),
+            _ => Self::english(),
+        }
+    }
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+/// Sets the text of fli output on the current thread
+pub fn set_messages(messages: Messages) {
+    MESSAGES.with(|current| *current.borrow_mut() = messages);
+}
+
+/// Gets the text of fli output on the current thread
+pub fn messages() -> Messages {
+    MESSAGES.with(|messages| messages.borrow().clone())
+}
+
+/// Sets the messages, when there are some, until the returned guard is dropped
+pub(crate) fn scoped_messages(messages: Option<Messages>) -> RestoreMessages {
+    RestoreMessages(messages.map(|messages| MESSAGES.with(|current| current.replace(messages))))
+}
+
+/// Puts back the messages replaced by `scoped_messages`
+pub(crate) struct RestoreMessages(Option<Messages>);
+
+impl Drop for RestoreMessages {
+    fn drop(&mut self) {
+        if let Some(messages) = self.0.take() {
+            set_messages(messages);
+        }
+    }
+}
+
+/// Replaces the `{name}` like placeholders of a message with their values, in one pass so values are left as they are
+pub(crate) fn fill(message: &str, values: &[(&str, &str)]) -> String {
+    let mut filled = String::new();
+    let mut rest = message;
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let value = rest.find('}').and_then(|end| {
+            let placeholder = &rest[1..end];
+            values
+                .iter()
+                .find(|(name, _)| *name == placeholder)
+                .map(|(_, value)| (end, value))
+        });
+        match value {
+            Some((end, value)) => {
+                filled.push_str(value);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                filled.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
@@ -0,0 +1,96 @@
+use std::{env, path::PathBuf};
+
+/// Gets the directory the config files of an app go in
+///
+/// | platform | directory |
+/// |----------|-----------|
+/// | Linux and other unix | `$XDG_CONFIG_HOME/<app>` or `~/.config/<app>` |
+/// | macOS | `$XDG_CONFIG_HOME/<app>` or `~/Library/Application Support/<app>` |
+/// | Windows | `%APPDATA%\<app>` |
+/// # Arguments
+/// * `app` - The name of the app, like `Fli::get_app_name`
+///
+/// # Example
+/// ```
+/// if let Some(dir) = fli::paths::config_dir("myapp") {
+///     println!("reading {}", dir.join("config.toml").display());
+/// }
+/// ```
+///
+/// # Returns
+/// * `Option<PathBuf>` - The directory, none if the home directory is unknown
+pub fn config_dir(app: &str) -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        env_path("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        env_path("XDG_CONFIG_HOME").or_else(|| home_path("Library/Application Support"))
+    } else {
+        env_path("XDG_CONFIG_HOME").or_else(|| home_path(".config"))
+    };
+    Some(base?.join(app))
+}
+
+/// Gets the directory the cache files of an app go in, files that can be deleted at any time
+///
+/// | platform | directory |
+/// |----------|-----------|
+/// | Linux and other unix | `$XDG_CACHE_HOME/<app>` or `~/.cache/<app>` |
+/// | macOS | `$XDG_CACHE_HOME/<app>` or `~/Library/Caches/<app>` |
+/// | Windows | `%LOCALAPPDATA%\<app>\cache` |
+/// # Arguments
+/// * `app` - The name of the app, like `Fli::get_app_name`
+///
+/// # Returns
+/// * `Option<PathBuf>` - The directory, none if the home directory is unknown
+pub fn cache_dir(app: &str) -> Option<PathBuf> {
+    if cfg!(windows) {
+        return Some(env_path("LOCALAPPDATA")?.join(app).join("cache"));
+    }
+    let base = if cfg!(target_os = "macos") {
+        env_path("XDG_CACHE_HOME").or_else(|| home_path("Library/Caches"))
+    } else {
+        env_path("XDG_CACHE_HOME").or_else(|| home_path(".cache"))
+    };
+    Some(base?.join(app))
+}
+
+/// Gets the directory the data files of an app go in, like databases or downloads
+///
+/// | platform | directory |
+/// |----------|-----------|
+/// | Linux and other unix | `$XDG_DATA_HOME/<app>` or `~/.local/share/<app>` |
+/// | macOS | `$XDG_DATA_HOME/<app>` or `~/Library/Application Support/<app>` |
+/// | Windows | `%LOCALAPPDATA%\<app>\data` |
+/// # Arguments
+/// * `app` - The name of the app, like `Fli::get_app_name`
+///
+/// # Returns
+/// * `Option<PathBuf>` - The directory, none if the home directory is unknown
+pub fn data_dir(app: &str) -> Option<PathBuf> {
+    if cfg!(windows) {
+        return Some(env_path("LOCALAPPDATA")?.join(app).join("data"));
+    }
+    let base = if cfg!(target_os = "macos") {
+        env_path("XDG_DATA_HOME").or_else(|| home_path("Library/Application Support"))
+    } else {
+        env_path("XDG_DATA_HOME").or_else(|| home_path(".local/share"))
+    };
+    Some(base?.join(app))
+}
+
+/// Gets the path in an environment variable, none if it is not set or not absolute like XDG asks
+fn env_path(name: &str) -> Option<PathBuf> {
+    env::var_os(name)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+}
+
+/// Gets a path in the home directory
+fn home_path(relative: &str) -> Option<PathBuf> {
+    let home = if cfg!(windows) {
+        env_path("USERPROFILE")
+    } else {
+        env_path("HOME")
+    };
+    Some(home?.join(relative))
+}
@@ -0,0 +1,233 @@
+use std::{
+    cell::{Cell, RefCell},
+    io::{self, BufRead},
+};
+
+use crate::{
+    display,
+    messages::{fill, messages},
+    value::FliValue,
+};
+
+thread_local! {
+    /// The reader answers are read from, stdin when not set
+    static INPUT: RefCell<Option<Box<dyn BufRead>>> = RefCell::new(None);
+    /// Whether `confirm` answers yes without asking
+    static ASSUME_YES: Cell<bool> = const { Cell::new(false) };
+    /// Whether prompts fail (or take their default) instead of asking
+    static NO_INPUT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Replaces where answers to prompts are read from on the current thread
+/// # Arguments
+/// * `reader` - The reader to use instead of stdin
+///
+/// # Example
+/// ```
+/// fli::prompt::set_input(Box::new(std::io::Cursor::new("yes\n")));
+/// assert!(fli::prompt::confirm("Continue?", false));
+/// fli::prompt::reset_input();
+/// ```
+pub fn set_input(reader: Box<dyn BufRead>) {
+    INPUT.with(|input| *input.borrow_mut() = Some(reader));
+}
+
+/// Sends prompts on the current thread back to reading stdin
+pub fn reset_input() {
+    INPUT.with(|input| *input.borrow_mut() = None);
+}
+
+/// Makes `confirm` answer yes without asking on the current thread, like a `--yes` flag
+///
+/// fli turns it on while the callbacks run when the app has a `--yes` option and it is passed
+pub fn set_assume_yes(assume_yes: bool) {
+    ASSUME_YES.with(|c| c.set(assume_yes));
+}
+
+/// Makes prompts on the current thread not ask, like a `--no-input` flag
+///
+/// `confirm` takes its default while the other prompts return an error,
+/// fli turns it on while the callbacks run when the app has a `--no-input` option and it is passed
+pub fn set_no_input(no_input: bool) {
+    NO_INPUT.with(|c| c.set(no_input));
+}
+
+/// Asks a yes or no question, asking again until the answer is one
+/// # Arguments
+/// * `question` - The question to ask
+/// * `default` - The answer when nothing is typed or there is nothing to read
+///
+/// # Example
+/// ```no_run
+/// if fli::prompt::confirm("Remove 'notes.txt'?", false) {
+///     std::fs::remove_file("notes.txt").unwrap();
+/// }
+/// ```
+///
+/// # Returns
+/// * `bool` - Whether the answer is yes
+pub fn confirm(question: &str, default: bool) -> bool {
+    if ASSUME_YES.with(Cell::get) {
+        return true;
+    }
+    if NO_INPUT.with(Cell::get) {
+        return default;
+    }
+    let messages = messages();
+    let hint = if default { &messages.confirm_default_yes } else { &messages.confirm_default_no };
+    // the word or its first letter, in English or the language of the messages
+    let is_answer = |answer: &str, english: &str, word: &str| {
+        [english, word]
+            .iter()
+            .any(|word| answer == *word || answer.chars().eq(word.chars().take(1)))
+    };
+    loop {
+        display::print(format_args!("{question} {hint} "));
+        let Some(answer) = read_line() else {
+            return default;
+        };
+        let answer = answer.to_lowercase();
+        if answer.is_empty() {
+            return default;
+        } else if is_answer(&answer, "yes", &messages.yes) {
+            return true;
+        } else if is_answer(&answer, "no", &messages.no) {
+            return false;
+        }
+        crate::fli_println!("{}", messages.answer_yes_or_no);
+    }
+}
+
+/// Asks for a value, asking again until it can be parsed
+/// # Arguments
+/// * `question` - The question to ask
+///
+/// # Example
+/// ```no_run
+/// let port: u16 = fli::prompt::input("Port").unwrap();
+/// ```
+///
+/// # Returns
+/// * `Result<T, String>` - The value, an error if there is nothing to read or input is turned off
+pub fn input<T: FliValue>(question: &str) -> Result<T, String> {
+    if NO_INPUT.with(Cell::get) {
+        return Err(fill(&messages().answer_required, &[("question", question)]));
+    }
+    loop {
+        display::print(format_args!("{question}: "));
+        let answer = read_line().ok_or_else(no_input(question))?;
+        match T::parse(&answer) {
+            Ok(value) => return Ok(value),
+            Err(message) => crate::fli_println!("{message}"),
+        }
+    }
+}
+
+/// Asks for a secret like a password or token, without showing what is typed
+/// # Arguments
+/// * `question` - The question to ask
+///
+/// # Example
+/// ```no_run
+/// let token = fli::prompt::password("Token").unwrap();
+/// ```
+///
+/// # Returns
+/// * `Result<String, String>` - The secret, an error if there is nothing to read or input is turned off
+pub fn password(question: &str) -> Result<String, String> {
+    if NO_INPUT.with(Cell::get) {
+        return Err(fill(&messages().answer_required, &[("question", question)]));
+    }
+    display::print(format_args!("{question}: "));
+    if INPUT.with(|input| input.borrow().is_some()) {
+        return read_line().ok_or_else(no_input(question));
+    }
+    let _ = io::Write::flush(&mut io::stdout());
+    rpassword::read_password().map_err(|error| format!("{question} : {error}"))
+}
+
+/// Asks to pick one of the items by its number, asking again until the number is valid
+/// # Arguments
+/// * `question` - The question to ask
+/// * `items` - The items to pick from
+///
+/// # Example
+/// ```no_run
+/// let shells = ["bash", "zsh", "fish"];
+/// let index = fli::prompt::select("Shell", &shells).unwrap();
+/// println!("using {}", shells[index]);
+/// ```
+///
+/// # Returns
+/// * `Result<usize, String>` - The index of the item, an error if there are no items, nothing to read or input is turned off
+pub fn select<S: AsRef<str>>(question: &str, items: &[S]) -> Result<usize, String> {
+    if items.is_empty() {
+        return Err(fill(&messages().nothing_to_select, &[("question", question)]));
+    }
+    if NO_INPUT.with(Cell::get) {
+        return Err(fill(&messages().answer_required, &[("question", question)]));
+    }
+    crate::fli_println!("{question}");
+    for (index, item) in items.iter().enumerate() {
+        crate::fli_println!("  {}) {}", index + 1, item.as_ref());
+    }
+    loop {
+        let messages = messages();
+        display::print(format_args!("{}", fill(&messages.select, &[("count", &items.len().to_string())])));
+        let answer = read_line().ok_or_else(no_input(question))?;
+        match answer.parse::<usize>() {
+            Ok(number) if (1..=items.len()).contains(&number) => return Ok(number - 1),
+            _ => crate::fli_println!("{}", fill(&messages.not_a_choice, &[("answer", &answer)])),
+        }
+    }
+}
+
+/// Turns `set_assume_yes` and `set_no_input` on until the returned guard is dropped
+pub(crate) fn scoped_flags(assume_yes: bool, no_input: bool) -> RestoreFlags {
+    RestoreFlags {
+        assume_yes: ASSUME_YES.with(|c| c.replace(c.get() || assume_yes)),
+        no_input: NO_INPUT.with(|c| c.replace(c.get() || no_input)),
+    }
+}
+
+/// Puts back the flags replaced by `scoped_flags`, even if a callback exits
+pub(crate) struct RestoreFlags {
+    assume_yes: bool,
+    no_input: bool,
+}
+
+impl Drop for RestoreFlags {
+    fn drop(&mut self) {
+        ASSUME_YES.with(|c| c.set(self.assume_yes));
+        NO_INPUT.with(|c| c.set(self.no_input));
+    }
+}
+
+/// Checks if the input is stdin, not replaced with `set_input`, and a terminal
+#[cfg(feature = "readline")]
+pub(crate) fn is_terminal_input() -> bool {
+    use std::io::IsTerminal;
+    INPUT.with(|input| input.borrow().is_none()) && io::stdin().is_terminal()
+}
+
+/// Reads a trimmed line of input, none at the end of the input
+pub(crate) fn read_line() -> Option<String> {
+    let mut line = String::new();
+    let read = INPUT.with(|input| match input.borrow_mut().as_mut() {
+        Some(reader) => reader.read_line(&mut line),
+        None => {
+            // the question is written without a newline
+            let _ = io::Write::flush(&mut io::stdout());
+            io::stdin().lock().read_line(&mut line)
+        }
+    });
+    match read {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line.trim().to_string()),
+    }
+}
+
+/// Makes the error for a prompt that has nothing to read
+fn no_input(question: &str) -> impl FnOnce() -> String + '_ {
+    move || fill(&messages().no_input, &[("question", question)])
+}
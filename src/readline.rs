@@ -0,0 +1,63 @@
+use rustyline::{
+    completion::Completer, error::ReadlineError, highlight::Highlighter, hint::Hinter, history::DefaultHistory,
+    Context, Editor, Helper,
+};
+
+use crate::{display, prompt, Fli};
+
+/// Completes the words of a `Fli::repl` line from the commands and options of the app, see `Fli::complete`
+struct ReplHelper {
+    app: Fli,
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<String>)> {
+        let line = &line[..pos];
+        let start = line.rfind(char::is_whitespace).map_or(0, |index| index + 1);
+        Ok((start, self.app.complete(line)))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl rustyline::validate::Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+/// Reads the lines of `Fli::repl` from the terminal, with the arrow keys going through the lines run and tab completing
+pub(crate) struct LineEditor {
+    editor: Editor<ReplHelper, DefaultHistory>,
+}
+
+impl LineEditor {
+    /// Makes an editor completing from the commands and options of `app`
+    /// none when the input or output is not a terminal, like in tests or with `prompt::set_input`
+    pub(crate) fn new(app: &Fli) -> Option<Self> {
+        if !prompt::is_terminal_input() || !display::is_output_terminal() {
+            return None;
+        }
+        let mut editor = Editor::new().ok()?;
+        editor.set_helper(Some(ReplHelper { app: app.clone() }));
+        Some(Self { editor })
+    }
+
+    /// Reads a line, an empty one on Ctrl-C and none at the end of the input (Ctrl-D)
+    pub(crate) fn read_line(&mut self, prompt: &str) -> Option<String> {
+        match self.editor.readline(prompt) {
+            Ok(line) => Some(line.trim().to_string()),
+            Err(ReadlineError::Interrupted) => Some(String::new()),
+            Err(_) => None,
+        }
+    }
+
+    /// Keeps a line run for the arrow keys
+    pub(crate) fn add_history(&mut self, line: &str) {
+        let _ = self.editor.add_history_entry(line);
+    }
+}
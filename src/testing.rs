@@ -0,0 +1,93 @@
+use std::cell::Cell;
+
+use crate::{catch_exit, display, error::FliError, Action, Fli};
+
+thread_local! {
+    /// The exit code of the last error reported while invoking an app, from the command it happened in
+    static ERROR_EXIT_CODE: Cell<Option<i32>> = const { Cell::new(None) };
+}
+
+/// Wraps an app so it can be invoked repeatedly with different args inside a test
+///
+/// Calls to `fli::exit` end the invocation instead of the test process
+/// and everything printed through `fli::display` is captured
+///
+/// # Example
+/// ```
+/// use fli::{testing::TestApp, Fli};
+///
+/// let mut app = Fli::init("my-app", "a sample app");
+/// app.option("-n --name, <>", "Your name", |_x| {});
+///
+/// let mut test_app = TestApp::new(app);
+/// let output = test_app.invoke(["--name"]);
+/// assert_eq!(output.status, 2);
+/// assert!(output.error.is_some());
+/// assert!(test_app.invoke(["--help"]).stdout.contains("Your name"));
+/// ```
+pub struct TestApp {
+    app: Fli,
+}
+
+/// The result of a single `TestApp::invoke`
+#[derive(Debug)]
+pub struct TestOutput {
+    /// The exit status the app would have ended with, `0` on success, the exit code of the error
+    /// (see `Fli::set_exit_code` and `Fli::on_error`) or the code passed to `fli::exit` or `Fli::request_exit`
+    pub status: i32,
+    /// The error returned while parsing the args, if any
+    pub error: Option<FliError>,
+    /// Everything the app printed to the fli output
+    pub stdout: String,
+    /// Everything the app printed to the fli error output
+    pub stderr: String,
+}
+
+impl TestApp {
+    /// Wraps the app for testing
+    pub fn new(app: Fli) -> Self {
+        Self { app }
+    }
+
+    /// Runs the app with the given args, the app name is added as the runner
+    /// # Arguments
+    /// * `args` - The args as a user would type them after the app name
+    ///
+    /// # Returns
+    /// * `TestOutput` - The exit status, error and output of the run
+    pub fn invoke<I, S>(&mut self, args: I) -> TestOutput
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut argv = vec![self.app.get_app_name()];
+        argv.extend(args.into_iter().map(Into::into));
+        self.app.set_args(argv);
+        ERROR_EXIT_CODE.with(|code| code.set(None));
+        let report = |app: &Fli, error: &FliError| {
+            let code = app.error_exit_code(error);
+            ERROR_EXIT_CODE.with(|cell| cell.set(Some(code)));
+        };
+        let (result, stdout, stderr) = display::capture(|| catch_exit(|| self.app.try_run_reporting(report)));
+        let (status, error) = match result {
+            Ok(Ok(Action::Continue)) => (0, None),
+            Ok(Ok(Action::Exit(status))) => (status, None),
+            Ok(Err(error)) => {
+                let status = ERROR_EXIT_CODE.with(Cell::take).unwrap_or_else(|| self.app.error_exit_code(&error));
+                (status, Some(error))
+            }
+            Err(status) => (status, None),
+        };
+        TestOutput {
+            status,
+            error,
+            stdout,
+            stderr,
+        }
+    }
+
+    /// Gets the wrapped app
+    pub fn app(&self) -> &Fli {
+        &self.app
+    }
+}
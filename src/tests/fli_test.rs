@@ -1,4 +1,20 @@
-use crate::{fli::Fli, add, levenshtein_distance};
+use std::{
+    cell::RefCell,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use crate::{
+    add, catch_exit, completions, Action, ArgAction,
+    display::{self, should_colorize, wrap_text, Align, ColorPolicy, Overflow, ProgressBar, Spinner, Table, TableStyle, Theme},
+    fli::Fli,
+    is_negative_number, levenshtein_distance,
+    messages::{self, Messages},
+    parse_duration, parse_size, prompt, split_shell_words,
+    testing::TestApp,
+    DefaultErrorRenderer, DefaultHelpRenderer, ErrorRenderer, FliError, FliErrorKind, FliValue, HelpOrder, HelpRenderer,
+    Input, OptionInfo, UnknownOptionPolicy, ValueHint,
+};
 
 #[test]
 pub fn test_add() {
@@ -50,9 +66,2238 @@ pub fn test_fli_init() {
 
 // test if the `Fli::init_from_toml` is working correctly
 #[test]
+#[allow(deprecated)]
 pub fn test_fli_init_from_toml() {
     let fli = Fli::init_from_toml();
     let toml_name = std::env::var("CARGO_PKG_NAME").unwrap();
     assert_eq!(fli.get_app_name(), toml_name);
 }
 
+
+// test that repeated `key=value` occurrences are collected by `Fli::get_map`
+#[test]
+pub fn test_get_map() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-D --define, <>", "testing", |_app| {});
+    fli.option("-e --env, [...]", "testing", |_app| {});
+    fli.option("-q --quiet", "testing", |_app| {});
+    fli.set_args(
+        ["fli-test", "-D", "name=fli", "--define", "mode=dev=fast", "-e", "a=1", "b=2", "-q"]
+            .map(String::from)
+            .to_vec(),
+    );
+    let map = fli.get_map("define".to_string()).unwrap();
+    assert_eq!(map.get("name").unwrap(), "fli");
+    assert_eq!(map.get("mode").unwrap(), "dev=fast");
+    let env = fli.get_map("-e".to_string()).unwrap();
+    assert_eq!(env.len(), 2);
+    assert!(fli.get_map("quiet".to_string()).is_err());
+}
+
+// test the parse_duration function
+#[test]
+pub fn test_parse_duration() {
+    assert_eq!(parse_duration("30s"), Some(Duration::from_secs(30)));
+    assert_eq!(parse_duration("5m"), Some(Duration::from_secs(300)));
+    assert_eq!(parse_duration("2h30m"), Some(Duration::from_secs(9000)));
+    assert_eq!(parse_duration("1500ms"), Some(Duration::from_millis(1500)));
+    assert_eq!(parse_duration("45"), Some(Duration::from_secs(45)));
+    assert_eq!(parse_duration("5x"), None);
+    assert_eq!(parse_duration("m5"), None);
+    assert_eq!(parse_duration(""), None);
+}
+
+// test the parse_size function
+#[test]
+pub fn test_parse_size() {
+    assert_eq!(parse_size("512"), Some(512));
+    assert_eq!(parse_size("4K"), Some(4096));
+    assert_eq!(parse_size("1.5MiB"), Some(1_572_864));
+    assert_eq!(parse_size("10MB"), Some(10_000_000));
+    assert_eq!(parse_size("2 g"), Some(2_147_483_648));
+    assert_eq!(parse_size("12XB"), None);
+    assert_eq!(parse_size("MB"), None);
+}
+
+// test reading typed values through `Fli::get_value`
+#[test]
+pub fn test_get_value() {
+    struct Semver(u32, u32, u32);
+    impl FliValue for Semver {
+        fn parse(value: &str) -> Result<Self, String> {
+            let parts: Vec<u32> = value.split('.').filter_map(|p| p.parse().ok()).collect();
+            match parts[..] {
+                [major, minor, patch] => Ok(Semver(major, minor, patch)),
+                _ => Err(format!("'{value}' is not major.minor.patch")),
+            }
+        }
+        fn type_name() -> &'static str {
+            "version"
+        }
+    }
+
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-p --port, <>", "testing", |_app| {});
+    fli.option("-r --release, <>", "testing", |_app| {});
+    fli.option("-t --timeout, []", "testing", |_app| {});
+    fli.set_args(["fli-test", "-p", "8080", "-r", "1.2.x", "-t", "2m"].map(String::from).to_vec());
+    assert_eq!(fli.get_value::<u16>("port".to_string()), Ok(8080));
+    assert!(fli.get_value::<bool>("port".to_string()).is_err());
+    assert_eq!(fli.get_value::<Duration>("t".to_string()), Ok(Duration::from_secs(120)));
+    let err = fli.get_value::<Semver>("release".to_string()).err().unwrap();
+    assert_eq!(err, "Invalid version for --release: '1.2.x' is not major.minor.patch");
+    assert!(Semver::parse("1.2.3").is_ok_and(|v| v.0 == 1 && v.1 == 2 && v.2 == 3));
+}
+
+// test that `--` ends the options anywhere even when trailing args are not allowed
+#[test]
+pub fn test_separator_anywhere() {
+    let mut fli = Fli::init("fli-test", "cat");
+    fli.option("-n --number", "testing", |app| crate::fli_println!("{:?}", app.trailing_args()));
+    fli.default(|app| crate::fli_println!("{:?}", app.trailing_args()));
+    fli.command("run", "testing")
+        .default(|app| crate::fli_println!("run {:?}", app.trailing_args()));
+    let mut test_app = TestApp::new(fli);
+
+    let output = test_app.invoke(["--", "file.txt"]);
+    assert!(output.error.is_none());
+    assert_eq!(output.stdout, "[\"file.txt\"]\n");
+    let output = test_app.invoke(["file1", "-n", "--", "-x", "run"]);
+    assert!(output.error.is_none());
+    assert_eq!(output.stdout, "[\"-x\", \"run\"]\n");
+    assert_eq!(test_app.invoke(["run", "--", "-x"]).stdout, "run [\"-x\"]\n");
+}
+
+// test that positional args are told apart from options and their values, and capped
+#[test]
+pub fn test_max_positional_args() {
+    let mut fli = Fli::init("fli-test", "cat");
+    fli.option("-o --output, <>", "testing", |_app| {});
+    fli.option("-n --number", "testing", |_app| {});
+    fli.set_args(["fli-test", "a.txt", "-o", "out.txt", "-n", "b.txt", "--", "-c.txt"].map(String::from).to_vec());
+    assert_eq!(fli.positional_args(), vec!["a.txt", "b.txt", "-c.txt"]);
+
+    fli.command("cat", "testing")
+        .max_positional_args(1)
+        .default(|app| crate::fli_println!("{:?}", app.positional_args()));
+    let mut test_app = TestApp::new(fli);
+    assert_eq!(test_app.invoke(["cat", "a.txt"]).stdout, "[\"a.txt\"]\n");
+    let output = test_app.invoke(["cat", "a.txt", "b.txt", "c.txt"]);
+    assert_eq!(
+        output.error.as_ref().map(FliError::inner),
+        Some(&FliError::TooManyArguments { argument: "b.txt".to_string(), expected: 1, got: 3, index: 3 })
+    );
+    assert_eq!(test_app.invoke(["cat", "a.txt", "b.txt", "--help"]).status, 0);
+}
+
+// test that the command tree can be read and walked
+#[test]
+pub fn test_walk() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-n --name, <>", "Your name", |_app| {});
+    fli.option("--dry-run", "Do nothing", |_app| {});
+    fli.command("remote", "Manage remotes")
+        .argument("[filter]", "testing")
+        .command("add", "testing");
+    fli.command("build", "testing");
+
+    let name = fli.options().into_iter().find(|option| option.long == "--name").unwrap();
+    assert_eq!(
+        name,
+        OptionInfo {
+            long: "--name".to_string(),
+            short: Some("-n".to_string()),
+            param: "<>".to_string(),
+            description: "Your name".to_string(),
+            value_hint: String::new(),
+            choices: vec![],
+        }
+    );
+    let dry_run = fli.options().into_iter().find(|option| option.long == "--dry-run").unwrap();
+    assert_eq!((dry_run.short, dry_run.param.as_str()), (None, ""));
+    let remote = fli.commands()[0];
+    assert_eq!(remote.get_description(), "Manage remotes");
+    assert_eq!(remote.arguments()[0].name, "filter");
+    assert_eq!(remote.arguments()[0].param, "[]");
+
+    let mut visited = vec![];
+    fli.walk(|command, depth| visited.push(format!("{depth} {}", command.get_app_name())));
+    assert_eq!(visited, ["0 fli-test", "1 remote", "2 add", "1 build"]);
+}
+
+// test that an app rebuilt from its spec has the same spec
+#[test]
+pub fn test_to_spec() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_version("1.2.0");
+    fli.option("-n --name, <>", "Your name", |_app| {});
+    fli.option("--dry-run", "Do nothing", |_app| {});
+    fli.command("cp", "Copy files")
+        .option("-f --force", "testing", |_app| {})
+        .argument("<source>...", "testing")
+        .argument("[dest]", "testing")
+        .command("deep", "testing");
+
+    let spec = fli.to_spec();
+    assert_eq!(spec.version, "1.2.0");
+    assert_eq!(spec.commands[0].name, "cp");
+    assert_eq!(spec.commands[0].commands[0].name, "deep");
+    assert_eq!(Fli::from_spec(&spec).to_spec(), spec);
+}
+
+// test that the spec serializes with its options and commands
+#[cfg(all(feature = "serde", feature = "json"))]
+#[test]
+pub fn test_spec_serialize() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-n --name, <>", "Your name", |_app| {});
+    let json = serde_json::to_value(fli.to_spec()).unwrap();
+    assert_eq!(json["name"], "fli-test");
+    let options = json["options"].as_array().unwrap();
+    let name = options.iter().find(|option| option["long"] == "--name").unwrap();
+    assert_eq!(name["short"], "-n");
+    assert_eq!(name["param"], "<>");
+    assert_eq!(json["commands"], serde_json::json!([]));
+}
+
+// test that a serialized spec deserializes back, skipping unknown fields
+#[cfg(all(feature = "serde", feature = "json"))]
+#[test]
+pub fn test_spec_deserialize() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-n --name, <>", "Your name", |_app| {});
+    fli.argument("source", "testing");
+    fli.command("cp", "Copy files");
+    let spec = fli.to_spec();
+    let mut json = serde_json::to_value(&spec).unwrap();
+    json["generated_by"] = serde_json::json!("fli");
+    let loaded: crate::CliSpec = serde_json::from_value(json).unwrap();
+    assert_eq!(loaded, spec);
+    let partial: crate::CliSpec = serde_json::from_str(r#"{"name": "tool"}"#).unwrap();
+    assert_eq!(partial.name, "tool");
+    assert!(partial.options.is_empty());
+}
+
+// test that the carapace and Fig specs list the commands, options and positional args
+#[test]
+pub fn test_completion_specs() {
+    let mut fli = Fli::init("fli-test", "say \"hi\"");
+    fli.option("-n --name, <>", "Your name", |_app| {});
+    fli.option("--tag, [...]", "testing", |_app| {});
+    fli.command("cp", "Copy files")
+        .argument("<source>...", "testing")
+        .command("deep", "testing");
+
+    let yaml = completions::carapace_spec(&fli);
+    assert!(yaml.starts_with("name: \"fli-test\"\ndescription: \"say \\\"hi\\\"\"\nflags:\n"));
+    assert!(yaml.contains("\n  \"-n, --name=\": \"Your name\"\n"));
+    assert!(yaml.contains("\n  \"--tag?\": \"testing\"\n"));
+    assert!(yaml.contains("\ncommands:\n  - name: \"cp\"\n    description: \"Copy files\"\n"));
+    assert!(yaml.contains("\n    commands:\n      - name: \"deep\"\n"));
+
+    let ts = completions::fig_spec(&fli);
+    assert!(ts.contains("\n  subcommands: [\n    {\n      name: \"cp\",\n"));
+    assert!(ts.contains("args: { name: \"tag\", isOptional: true, isVariadic: true }"));
+    assert!(ts.contains("\n      args: [\n        { name: \"source\", isVariadic: true },\n      ],\n"));
+    assert!(ts.ends_with("};\n\nexport default completionSpec;\n"));
+}
+
+// test that value hints reach the spec, the completion specs and ask_value
+#[test]
+pub fn test_value_hints() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-o --output, <>", "testing", |_app| {});
+    fli.value_hint("-o", ValueHint::FilePath);
+    fli.option("-f --format, <>", "Format", |_app| {});
+    fli.value_hint("format", ValueHint::Custom(|| vec!["json".to_string(), "yaml".to_string()]));
+
+    let spec = fli.to_spec();
+    let format = spec.options.iter().find(|option| option.long == "--format").unwrap();
+    assert_eq!((format.value_hint.as_str(), format.choices.clone()), ("choice", vec!["json".to_string(), "yaml".to_string()]));
+    assert_eq!(Fli::from_spec(&spec).to_spec(), spec);
+
+    let yaml = completions::carapace_spec(&fli);
+    assert!(yaml.contains("\ncompletion:\n  flag:\n"));
+    assert!(yaml.contains("\n    output: [\"$files\"]\n"));
+    assert!(yaml.contains("\n    format: [\"json\", \"yaml\"]\n"));
+    assert!(yaml.contains("\n    color: [\"auto\", \"always\", \"never\"]\n"));
+    let ts = completions::fig_spec(&fli);
+    assert!(ts.contains("args: { name: \"output\", template: \"filepaths\" }"));
+    assert!(ts.contains("args: { name: \"format\", suggestions: [\"json\", \"yaml\"] }"));
+
+    fli.set_args(["fli-test", "-f", "toml"].map(String::from).to_vec());
+    assert_eq!(fli.ask_value("--format"), Ok("toml".to_string()));
+    fli.set_args(vec!["fli-test".to_string()]);
+    prompt::set_input(Box::new(std::io::Cursor::new("2\n")));
+    let (_, stdout, _) = display::capture(|| assert_eq!(fli.ask_value("--format"), Ok("yaml".to_string())));
+    prompt::reset_input();
+    assert!(stdout.starts_with("Format\n  1) json\n  2) yaml\n"));
+}
+
+// test that commands can be added under others from their path
+#[test]
+pub fn test_command_at() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.command("remote", "Manage remotes")
+        .command("list", "testing")
+        .default(|_app| crate::fli_println!("list"));
+    fli.command_at("remote add", "testing")
+        .default(|app| crate::fli_println!("{}", app.command_path().join(" ")));
+    fli.command_at(" branch  delete ", "testing")
+        .default(|app| crate::fli_println!("{}", app.command_path().join(" ")));
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(["remote", "add"]).stdout, "fli-test remote add\n");
+    assert_eq!(test_app.invoke(["remote", "list"]).stdout, "list\n");
+    assert!(test_app.invoke(["remote", "--help"]).stdout.contains("Manage remotes"));
+    assert_eq!(test_app.invoke(["branch", "delete"]).stdout, "fli-test branch delete\n");
+}
+
+// test that values after the first positional arg are not read as commands when positional args are preferred
+#[test]
+pub fn test_prefer_positionals_after_args() {
+    let build = |prefer: bool| {
+        let mut fli = Fli::init("fli-test", "cook");
+        fli.command("note", "testing")
+            .prefer_positionals_after_args(prefer)
+            .option("-t --tag, <>", "testing", |app| crate::fli_println!("{:?}", app.positional_args()))
+            .default(|app| crate::fli_println!("{:?}", app.positional_args()))
+            .command("list", "testing")
+            .default(|_app| crate::fli_println!("list"));
+        TestApp::new(fli)
+    };
+
+    assert_eq!(build(false).invoke(["note", "buy", "list"]).stdout, "list\n");
+
+    let mut test_app = build(true);
+    assert_eq!(test_app.invoke(["note", "list"]).stdout, "list\n");
+    assert_eq!(test_app.invoke(["note", "buy", "list"]).stdout, "[\"buy\", \"list\"]\n");
+    assert_eq!(test_app.invoke(["note", "-t", "list", "milk", "list"]).stdout, "[\"milk\", \"list\"]\n");
+}
+
+// test that described positional args are listed in the help screen and the usage line
+#[test]
+pub fn test_arguments_help() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.command("cp", "testing")
+        .argument("<source>...", "The files to copy")
+        .argument("[dest]", "Where to copy them");
+    let mut test_app = TestApp::new(fli);
+
+    let stdout = test_app.invoke(["cp", "--help"]).stdout;
+    assert!(stdout.contains("Usage: cp [options|commands] <source>... [dest]"));
+    let arguments = &stdout[stdout.find("Arguments:").unwrap()..];
+    let position = |text: &str| arguments.find(text).unwrap();
+    assert!(position("source") < position("Required Multiple"));
+    assert!(position("Required Multiple") < position("The files to copy"));
+    assert!(position("dest") < position("Optional"));
+    assert!(position("Where to copy them") < position("Options:"));
+
+    let stdout = test_app.invoke(["--help"]).stdout;
+    assert!(!stdout.contains("Arguments:"));
+    assert!(stdout.contains("[options|commands]\n"));
+}
+
+// test that too few positional args is an error with the usage line
+#[test]
+pub fn test_min_positional_args() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.command("cp", "testing")
+        .min_positional_args(2)
+        .default(|app| crate::fli_println!("{:?}", app.positional_args()));
+    fli.command("mv", "testing").min_positional_args(1).max_positional_args(2);
+    let mut test_app = TestApp::new(fli);
+
+    let output = test_app.invoke(["cp", "a.txt"]);
+    assert_eq!(
+        output.error.as_ref().map(FliError::inner),
+        Some(&FliError::MissingArguments {
+            command: "cp".to_string(),
+            expected: 2,
+            got: 1,
+            usage: "cp [options|commands] <arg> <arg> [arg]...".to_string()
+        })
+    );
+    assert_eq!(
+        output.error.unwrap().to_string(),
+        "error in 'fli-test cp': cp expects at least 2 arguments, got 1 (usage: cp [options|commands] <arg> <arg> [arg]...)"
+    );
+    assert_eq!(test_app.invoke(["cp", "a.txt", "b.txt"]).stdout, "[\"a.txt\", \"b.txt\"]\n");
+    assert!(test_app.invoke(["cp", "--help"]).stdout.contains("Usage: cp [options|commands] <arg> <arg> [arg]..."));
+    assert!(test_app.invoke(["mv", "--help"]).stdout.contains("Usage: mv [options|commands] <arg> [arg]\n"));
+}
+
+// test that `-` is read as a value standing for the standard input
+#[test]
+pub fn test_stdin_input() {
+    let mut fli = Fli::init("fli-test", "cat");
+    fli.option("-f --file, <...>", "testing", |app| {
+        let files = app.get_values("--file".to_string()).unwrap();
+        let inputs: Vec<Input> = files.iter().map(|file| Input::parse(file).unwrap()).collect();
+        crate::fli_println!("{inputs:?}");
+    });
+    let output = TestApp::new(fli.clone()).invoke(["-f", "notes.txt", "-"]);
+    assert!(output.error.is_none());
+    assert_eq!(output.stdout, "[Path(\"notes.txt\"), Stdin]\n");
+
+    fli.set_args(["fli-test", "--file", "-"].map(String::from).to_vec());
+    assert_eq!(fli.get_value::<Input>("file".to_string()), Ok(Input::Stdin));
+}
+
+// test that negative numbers are read as values and not as options
+#[test]
+pub fn test_negative_number_values() {
+    assert!(is_negative_number("-5"));
+    assert!(is_negative_number("-1.5"));
+    assert!(!is_negative_number("-"));
+    assert!(!is_negative_number("-v"));
+    assert!(!is_negative_number("-inf"));
+
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-c --count, <>", "testing", |_app| {});
+    fli.option("-r --range, <...>", "testing", |_app| {});
+    fli.option("-1 --one", "testing", |_app| {});
+    fli.set_args(["fli-test", "--count", "-5", "-r", "-1.5", "2", "-1"].map(String::from).to_vec());
+    assert!(fli.has_a_value("count".to_string()));
+    assert_eq!(fli.get_value::<i32>("count".to_string()), Ok(-5));
+    assert_eq!(fli.get_values("range".to_string()).unwrap(), vec!["-1.5", "2"]);
+    assert!(fli.is_passed("one".to_string()));
+}
+
+// test that trailing args are collected untouched and not parsed as options
+#[test]
+pub fn test_trailing_args() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-n --name, <>", "testing", |_app| {});
+    fli.option("-r --release", "testing", |_app| {});
+    fli.set_args(["fli-test", "-n", "james", "--", "cargo", "test", "--release"].map(String::from).to_vec());
+    // `--` ends the options even when trailing args are not allowed
+    assert_eq!(fli.trailing_args(), vec!["cargo", "test", "--release"]);
+    assert!(!fli.is_passed("release".to_string()));
+
+    fli.allow_trailing_args(true);
+    assert_eq!(fli.trailing_args(), vec!["cargo", "test", "--release"]);
+    assert!(!fli.is_passed("release".to_string()));
+    assert_eq!(fli.get_values("name".to_string()).unwrap(), vec!["james"]);
+
+    fli.set_args(["fli-test", "-n", "james", "cargo", "-r"].map(String::from).to_vec());
+    assert_eq!(fli.trailing_args(), vec!["cargo", "-r"]);
+    assert!(!fli.is_passed("release".to_string()));
+}
+
+// test that options are still parsed when they come after positional values
+#[test]
+pub fn test_options_after_positionals() {
+    static VERBOSE_RAN: AtomicBool = AtomicBool::new(false);
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-V --verbose", "testing", |_app| VERBOSE_RAN.store(true, Ordering::SeqCst));
+    fli.option("-o --output, <>", "testing", |_app| {});
+    fli.run_from(["fli-test", "file.txt", "-V", "other.txt", "-o", "out.txt"].map(String::from));
+    assert!(VERBOSE_RAN.load(Ordering::SeqCst));
+    assert_eq!(fli.get_values("output".to_string()).unwrap(), vec!["out.txt"]);
+    assert_eq!(fli.get_arg_at(1).unwrap(), "file.txt");
+}
+
+// test that `Fli::try_run_from` returns errors instead of exiting and dispatches to commands
+#[test]
+pub fn test_try_run_from() {
+    static GREETED: AtomicBool = AtomicBool::new(false);
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-n --name, <>", "testing", |_app| {});
+    fli.command("greet", "testing").option("-t --time, <>", "testing", |app| {
+        assert_eq!(app.get_values("time".to_string()).unwrap(), vec!["morning"]);
+        assert_eq!(app.get_arg_at(0).unwrap(), "greet");
+        GREETED.store(true, Ordering::SeqCst);
+    });
+
+    let result = fli.try_run_from(["fli-test", "--name"].map(String::from));
+    assert!(matches!(result, Err(FliError::MissingValue { option, .. }) if option == "--name"));
+
+    // the errors of commands name the command they happened in
+    let result = fli.try_run_from(["fli-test", "greet", "-t"].map(String::from));
+    let error = result.unwrap_err();
+    assert!(matches!(error.inner(), FliError::MissingValue { option, .. } if option == "--time"));
+    assert_eq!(error.to_string(), "error in 'fli-test greet': Invalid syntax : --time does not have a value");
+
+    let result = fli.try_run_from(["fli-test", "-n", "james", "greet", "-t", "morning"].map(String::from));
+    assert!(result.is_ok());
+    assert!(GREETED.load(Ordering::SeqCst));
+}
+
+// test that `TestApp` catches `fli::exit` and reports errors as a status
+#[test]
+pub fn test_testing_harness() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-n --name, <>", "testing", |_app| {});
+    fli.option("-q --quit", "testing", |_app| crate::exit(3));
+    let mut test_app = TestApp::new(fli);
+
+    let output = test_app.invoke(["-n", "james"]);
+    assert_eq!(output.status, 0);
+    assert!(output.error.is_none());
+
+    let output = test_app.invoke(["--name"]);
+    assert_eq!(output.status, 2);
+    assert_eq!(output.error, Some(FliError::MissingValue { option: "--name".to_string(), index: 1 }));
+
+    assert_eq!(test_app.invoke(["--quit"]).status, 3);
+    let output = test_app.invoke(["--help"]);
+    assert_eq!(output.status, 0);
+    assert!(output.stdout.contains("fli-test"));
+    assert!(output.stderr.is_empty());
+    assert_eq!(test_app.app().get_arg_at(1).unwrap(), "--help");
+}
+
+// test the `--color` option registered on every app and command
+#[test]
+pub fn test_color_option() {
+    assert!(!should_colorize(ColorPolicy::Never));
+    assert!(should_colorize(ColorPolicy::Always));
+    assert_eq!(ColorPolicy::parse("ALWAYS"), Ok(ColorPolicy::Always));
+
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-n --name, <>", "testing", |_app| {});
+    fli.command("greet", "testing");
+    let mut test_app = TestApp::new(fli);
+    let output = test_app.invoke(["--color", "sometimes", "-n", "james"]);
+    assert!(matches!(output.error, Some(FliError::InvalidValue { option, .. }) if option == "--color"));
+    assert_eq!(test_app.invoke(["-n", "james", "--color", "never"]).status, 0);
+    assert!(test_app.invoke(["greet", "--color"]).error.is_some());
+    assert_eq!(test_app.invoke(["--color=never", "greet"]).status, 0);
+    let output = test_app.invoke(["greet", "--color=sometimes"]);
+    assert!(matches!(output.error.as_ref().map(FliError::inner), Some(FliError::InvalidValue { option, .. }) if option == "--color"));
+    let help = test_app.invoke(["--help"]).stdout;
+    assert!(help.contains("--color   |       | Required"));
+}
+
+// test that an app's own `--color` option replaces the built-in one
+#[test]
+pub fn test_own_color_option() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-c --color, <>", "paint color", |app| {
+        crate::fli_println!("paint {}", app.get_values("color".to_string()).unwrap()[0]);
+    });
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(["-c", "red"]).stdout, "paint red\n");
+    assert_eq!(test_app.invoke(["--color", "always"]).stdout, "paint always\n");
+    assert!(test_app.invoke(["--color=red"]).error.is_some());
+    let help = test_app.invoke(["--help"]).stdout;
+    assert_eq!(help.matches("--color").count(), 1);
+    assert!(help.contains("paint color"));
+}
+
+// test that help descriptions wrap to the max width
+#[test]
+pub fn test_help_wrapping() {
+    assert_eq!(wrap_text("a b c", 10), vec!["a b c"]);
+    assert_eq!(wrap_text("", 10), vec![""]);
+    assert_eq!(wrap_text("abcdefghijkl mn", 5), vec!["abcde", "fghij", "kl mn"]);
+
+    display::set_max_width(60);
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option(
+        "-l --long, []",
+        "a rather long description that is not going to fit on one line of a sixty column screen",
+        |_app| {},
+    );
+    let output = TestApp::new(fli).invoke(["--help"]);
+    display::set_max_width(display::DEFAULT_MAX_WIDTH);
+    let lines: Vec<&str> = output.stdout.lines().filter(|line| line.contains("column") || line.contains("rather")).collect();
+    assert_eq!(lines.len(), 2);
+}
+
+// test that the help pager falls back to printing when not writing to a terminal
+#[test]
+pub fn test_help_pager_fallback() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.enable_help_pager();
+    fli.command("greet", "say hello to someone");
+    let mut test_app = TestApp::new(fli);
+    assert!(test_app.invoke(["--help"]).stdout.contains("say hello to someone"));
+    assert!(test_app.invoke(["greet", "--help"]).stdout.contains("Name: greet"));
+    assert!(!display::page("not a terminal"));
+}
+
+// test that help templates lay out the help sections
+#[test]
+pub fn test_help_template() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_help_template("{usage}\n--\n{subcommands} {unknown}");
+    fli.command("greet", "say hello to someone {options}");
+    fli.command("bye", "say bye").set_help_template("{description}");
+    let mut test_app = TestApp::new(fli);
+
+    let help = test_app.invoke(["--help"]).stdout;
+    assert!(help.starts_with("  Usage: fli-test [options|commands]\n--\n"));
+    assert!(help.contains("say hello to someone {options}"));
+    assert!(help.ends_with(" {unknown}\n"));
+    assert!(!help.contains("Options:"));
+
+    assert!(test_app.invoke(["greet", "--help"]).stdout.starts_with("  Usage: greet"));
+    assert_eq!(test_app.invoke(["bye", "--help"]).stdout, "  Description: say bye\n");
+}
+
+// test replacing the help screen with a help renderer
+#[test]
+pub fn test_help_renderer() {
+    struct CompactHelp;
+    impl HelpRenderer for CompactHelp {
+        fn render(&self, app: &Fli) -> String {
+            let options: Vec<String> = app.options().into_iter().map(|option| option.long).collect();
+            format!("usage: {}\noptions: {}\n", app.get_usage(), options.join(" "))
+        }
+    }
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_help_renderer(Box::new(CompactHelp));
+    fli.command("greet", "testing").option("-n --name, <>", "testing", |_app| {});
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(
+        test_app.invoke(["--help"]).stdout,
+        "usage: fli-test [options|commands]\noptions: --help --version --color\n"
+    );
+    assert!(test_app.invoke(["greet", "-h"]).stdout.starts_with("usage: greet [options|commands]\noptions: --help"));
+    let default_help = DefaultHelpRenderer.render(&Fli::init("fli-test", "cook"));
+    assert!(default_help.contains("Options:"));
+}
+
+// test that the help and version options stop the callbacks instead of exiting
+#[test]
+pub fn test_request_exit() {
+    static AFTER_RAN: AtomicBool = AtomicBool::new(false);
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_version("1.0.0");
+    fli.after(|_app| AFTER_RAN.store(true, Ordering::SeqCst));
+    fli.option("--fail", "testing", |app| app.request_exit(3));
+    fli.command("greet", "testing");
+
+    let (result, stdout, _) = display::capture(|| fli.try_run_from(["fli-test", "--version"].map(String::from)));
+    assert!(result.is_ok());
+    assert_eq!(stdout, "fli-test 1.0.0\n");
+    // the callbacks stop but the after hooks still run
+    assert!(AFTER_RAN.swap(false, Ordering::SeqCst));
+    let (action, stdout, _) = display::capture(|| fli.try_run_action());
+    assert_eq!(action.unwrap(), Action::Exit(0));
+    assert!(stdout.contains("fli-test 1.0.0"));
+    fli.set_args(["fli-test", "greet", "--help"].map(String::from).to_vec());
+    let (action, stdout, _) = display::capture(|| fli.try_run_action());
+    assert_eq!(action.unwrap(), Action::Exit(0));
+    assert!(stdout.contains("Name: greet"));
+    assert_eq!(TestApp::new(fli).invoke(["--fail"]).status, 3);
+}
+
+// test rendering the help screen without printing it
+#[test]
+pub fn test_render_help() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_help_template("{usage}\n{options}");
+    fli.option("-n --name, <>", "testing", |_app| {});
+    fli.command_at("remote add", "testing").option("-f --force", "testing", |_app| {});
+
+    let (help, stdout, _) = display::capture(|| fli.render_help());
+    assert!(stdout.is_empty());
+    let mut test_app = TestApp::new(fli.clone());
+    assert_eq!(help, test_app.invoke(["--help"]).stdout);
+    let help = fli.render_help_for("remote add").unwrap();
+    assert!(help.starts_with("  Usage: add"));
+    assert_eq!(help, test_app.invoke(["remote", "add", "--help"]).stdout);
+    assert!(fli.render_help_for("remote rm").is_none());
+}
+
+// test that the help command prints the help screen of the command at its path
+#[test]
+pub fn test_help_command() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.add_help_command();
+    fli.command_at("remote add", "testing").option("-f --force", "testing", |_app| {});
+    let mut test_app = TestApp::new(fli);
+
+    let help = test_app.invoke(["help", "remote", "add"]);
+    assert_eq!(help.status, 0);
+    assert_eq!(help.stdout, test_app.invoke(["remote", "add", "--help"]).stdout);
+    let help = test_app.invoke(["help"]).stdout;
+    assert_eq!(help, test_app.invoke(["--help"]).stdout);
+    assert!(help.contains("print help for fli-test or one of its commands"));
+
+    let unknown = test_app.invoke(["help", "remote", "ad"]);
+    assert_eq!(unknown.status, 1);
+    assert!(unknown.stdout.starts_with("Command not found: ad\n"));
+    assert!(unknown.stdout.contains("add"));
+}
+
+// test that -h prints the short help screen and --help the long one
+#[test]
+pub fn test_short_and_long_help() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.long_help("cook all the things, see the docs");
+    fli.option("-f --format, <>", "the format", |_app| {});
+    fli.option_long_help("--format", "the format of the output");
+    fli.value_hint("--format", ValueHint::Choices(vec!["json".to_string(), "yaml".to_string()]));
+    let mut test_app = TestApp::new(fli.clone());
+
+    let short = test_app.invoke(["-h"]).stdout;
+    assert_eq!(short, fli.render_short_help());
+    assert!(short.contains("Description: cook\n"));
+    assert!(short.contains("the format\n"));
+    assert!(!short.contains("possible values"));
+    let long = test_app.invoke(["--help"]).stdout;
+    assert_eq!(long, fli.render_help());
+    assert!(long.contains("Description: cook all the things, see the docs\n"));
+    assert!(long.contains("the format of the output [possible values: json, yaml]"));
+}
+
+// test the flag and typed option helpers
+#[test]
+pub fn test_typed_options() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.add_flag("-f", "--force", "testing");
+    fli.add_int_option("-p", "--port", "testing", Some(8080));
+    fli.add_float_option("", "--ratio", "testing", None);
+    fli.add_string_option("-n", "--name", "testing", Some("world"));
+    fli.default(|_app| {});
+    fli.after(|app| {
+        let port: i64 = app.get_value("port".to_string()).unwrap();
+        let name: String = app.get_value("name".to_string()).unwrap();
+        crate::fli_println!("{port} {name} {}", app.is_passed("--force".to_string()));
+    });
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(Vec::<String>::new()).stdout, "8080 world false\n");
+    assert_eq!(test_app.invoke(["-p", "80", "-n", "fli", "-f"]).stdout, "80 fli true\n");
+    let error = test_app.invoke(["--ratio", "half"]).error.unwrap();
+    assert!(matches!(error, FliError::InvalidValue { ref option, index: 2, .. } if option == "--ratio"));
+    assert!(matches!(test_app.invoke(["-p", "http"]).error.as_ref().map(FliError::inner), Some(FliError::InvalidValue { .. })));
+}
+
+// test reporting every error in the args at once
+#[test]
+pub fn test_collect_errors() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.collect_errors(true);
+    fli.add_int_option("-p", "--port", "testing", None);
+    fli.option("-n --name, <>", "testing", |_app| {});
+    fli.max_positional_args(0);
+    fli.default(|_app| crate::fli_println!("ran"));
+    let mut test_app = TestApp::new(fli);
+
+    let output = test_app.invoke(["--prot", "-p", "http", "extra", "-n"]);
+    assert_eq!(output.stdout, "");
+    let Some(FliError::Multiple { errors }) = output.error else {
+        panic!("expected many errors, got {:?}", output.error);
+    };
+    let kinds: Vec<FliErrorKind> = errors.iter().map(|error| error.kind()).collect();
+    assert_eq!(
+        kinds,
+        [
+            FliErrorKind::UnknownOption,
+            FliErrorKind::MissingValue,
+            FliErrorKind::InvalidValue,
+            FliErrorKind::TooManyArguments
+        ]
+    );
+    assert_eq!(
+        FliError::Multiple { errors: errors[..2].to_vec() }.to_string(),
+        "Unknown option --prot\nInvalid syntax : --name does not have a value"
+    );
+    let rendered = DefaultErrorRenderer.render(&Fli::init("fli-test", "cook"), &FliError::Multiple { errors });
+    assert!(rendered.contains("Unknown option --prot") && rendered.contains("Invalid value for --port"));
+    assert_eq!(
+        test_app.invoke(["-n"]).error,
+        Some(FliError::MissingValue { option: "--name".to_string(), index: 1 })
+    );
+    assert_eq!(test_app.invoke(Vec::<String>::new()).stdout, "ran\n");
+}
+
+// test that a bad `--color` value and the errors before a command are collected with the errors of the command
+#[test]
+pub fn test_collect_errors_before_command() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.collect_errors(true);
+    fli.command("serve", "testing").default(|_app| crate::fli_println!("serving"));
+    let mut test_app = TestApp::new(fli);
+
+    let output = test_app.invoke(["--color", "sometimes", "--prot", "serve", "--bad"]);
+    assert_eq!(output.stdout, "");
+    let Some(FliError::Multiple { errors }) = output.error.as_ref().map(FliError::inner) else {
+        panic!("expected many errors, got {:?}", output.error);
+    };
+    let kinds: Vec<FliErrorKind> = errors.iter().map(|error| error.kind()).collect();
+    assert_eq!(kinds, [FliErrorKind::InvalidValue, FliErrorKind::UnknownOption, FliErrorKind::UnknownOption]);
+    assert_eq!(test_app.invoke(["--color", "never", "serve"]).stdout, "serving\n");
+}
+
+// test reading Windows style options
+#[test]
+pub fn test_slash_options() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.allow_slash_options(true);
+    fli.option("-r --recursive", "testing", |_app| {});
+    fli.option("-o --output, <>", "testing", |_app| {});
+    fli.default(|_app| {});
+    fli.after(|app| {
+        crate::fli_println!(
+            "{} {:?} {:?}",
+            app.is_passed("recursive".to_string()),
+            app.get_values("output".to_string()),
+            app.positional_args()
+        )
+    });
+    fli.command("copy", "testing")
+        .option("-y --yes", "testing", |_app| {})
+        .default(|_app| {})
+        .after(|app| crate::fli_println!("{} {:?}", app.is_passed("yes".to_string()), app.positional_args()));
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(
+        test_app.invoke(["/r", "/output:backup", "/tmp"]).stdout,
+        "true Ok([\"backup\"]) [\"/tmp\"]\n"
+    );
+    assert_eq!(test_app.invoke(["/o=out.txt", "--", "/r"]).stdout, "false Ok([\"out.txt\"]) [\"/r\"]\n");
+    assert_eq!(test_app.invoke(["copy", "/y", "/r"]).stdout, "true [\"/r\"]\n");
+    assert!(test_app.invoke(["/?"]).stdout.contains("Options:"));
+}
+
+// test that `_` and `-` can be swapped in long options when allowed
+#[test]
+pub fn test_allow_any_separator() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.allow_any_separator(true);
+    fli.option("--max-depth, <>", "testing", |_app| {});
+    fli.option("--dry_run", "testing", |_app| {});
+    fli.default(|_app| {});
+    fli.after(|app| {
+        crate::fli_println!(
+            "{:?} {}",
+            app.get_values("max_depth".to_string()),
+            app.is_passed("dry-run".to_string())
+        )
+    });
+    fli.command("find", "testing").option("--follow-links", "testing", |_app| crate::fli_println!("follow"));
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(["--max_depth", "2", "--dry-run"]).stdout, "Ok([\"2\"]) true\n");
+    assert_eq!(test_app.invoke(["--max-depth", "3"]).stdout, "Ok([\"3\"]) false\n");
+    assert_eq!(test_app.invoke(["find", "--follow_links"]).stdout, "follow\n");
+    assert!(matches!(test_app.invoke(["--max_dept", "2"]).error.as_ref().map(FliError::inner), Some(FliError::UnknownOption { .. })));
+}
+
+// test that unknown args starting with `-` are errors unless hyphen values are allowed
+#[test]
+pub fn test_allow_hyphen_values() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.command("strict", "testing")
+        .option("-v --verbose", "testing", |_app| {})
+        .argument("<file>", "testing")
+        .default(|_app| {});
+    fli.command("exec", "testing")
+        .allow_hyphen_values(true)
+        .option("-c --command, <>", "testing", |_app| {})
+        .option("-v --verbose", "testing", |_app| {})
+        .default(|_app| {})
+        .after(|app| {
+            crate::fli_println!(
+                "{:?} {} {:?}",
+                app.get_values("command".to_string()),
+                app.is_passed("verbose".to_string()),
+                app.positional_args()
+            )
+        });
+    let mut test_app = TestApp::new(fli);
+
+    let error = test_app.invoke(["strict", "a.txt", "-x"]).error;
+    assert!(matches!(error.as_ref().map(FliError::inner), Some(FliError::UnknownOption { option, index: 3, .. }) if option == "-x"));
+    let error = test_app.invoke(["strict", "--verbos"]).error;
+    assert!(matches!(error.as_ref().map(FliError::inner), Some(FliError::UnknownOption { suggestions, .. }) if suggestions.contains(&"--verbose".to_string())));
+    assert_eq!(
+        test_app.invoke(["exec", "-c", "-rf", "-v", "-x", "--", "-v"]).stdout,
+        "Ok([\"-rf\"]) true [\"-x\", \"-v\"]\n"
+    );
+}
+
+// test that the last positional arg captures the args after it untouched
+#[test]
+pub fn test_last_positional_captures_rest() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.command("run", "testing")
+        .option("-q --quiet", "testing", |_app| {})
+        .argument("<program>", "testing")
+        .argument("[args]...", "testing")
+        .last_positional_captures_rest(true)
+        .default(|_app| {})
+        .after(|app| {
+            crate::fli_println!(
+                "{} {:?} {:?}",
+                app.is_passed("quiet".to_string()),
+                app.positional_args(),
+                app.trailing_args()
+            )
+        });
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(
+        test_app.invoke(["run", "-q", "cargo", "test", "--release", "-q"]).stdout,
+        "true [\"cargo\", \"test\", \"--release\", \"-q\"] [\"test\", \"--release\", \"-q\"]\n"
+    );
+    assert_eq!(test_app.invoke(["run", "ls"]).stdout, "false [\"ls\"] []\n");
+    assert_eq!(
+        test_app.invoke(["run", "--", "-x", "y"]).stdout,
+        "false [\"-x\", \"y\"] [\"-x\", \"y\"]\n"
+    );
+}
+
+// test that exclusive options can not be passed with other args
+#[test]
+pub fn test_exclusive_option() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_version("1.0.0");
+    fli.exclusive("--version");
+    fli.option("--completions, <>", "testing", |_app| {});
+    fli.exclusive("--completions");
+    fli.option("-v --verbose", "testing", |_app| {});
+    fli.default(|_app| {});
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(["--version"]).stdout, "fli-test 1.0.0\n");
+    assert!(test_app.invoke(["--completions", "bash"]).error.is_none());
+    assert!(test_app.invoke(["-v", "file.txt"]).error.is_none());
+    let exclusive = |option: &str, other: &str, index| {
+        Some(FliError::ExclusiveOption { option: option.to_string(), other: other.to_string(), index })
+    };
+    assert_eq!(test_app.invoke(["-v", "--version"]).error, exclusive("--version", "-v", 1));
+    assert_eq!(test_app.invoke(["--version", "file.txt"]).error, exclusive("--version", "file.txt", 2));
+    let output = test_app.invoke(["--completions", "bash", "--verbose"]);
+    assert_eq!(output.status, 2);
+    assert_eq!(output.error, exclusive("--completions", "--verbose", 3));
+    assert_eq!(output.error.unwrap().to_string(), "--completions can not be used with --verbose");
+}
+
+// test ending the values of options taking many values with a terminator
+#[test]
+pub fn test_value_terminator() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.value_terminator(";");
+    fli.command("cp", "testing")
+        .option("-f --files, <...>", "testing", |_app| {})
+        .option("-D --define, [...]", "testing", |_app| {})
+        .default(|_app| {})
+        .after(|app| {
+            crate::fli_println!(
+                "{:?} {:?} {:?}",
+                app.get_values("files".to_string()),
+                app.get_map("define".to_string()).map(|defines| defines.len()),
+                app.positional_args()
+            )
+        });
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(
+        test_app.invoke(["cp", "-f", "a.txt", "b.txt", ";", "backup", "-D", "a=1", ";", "c.txt"]).stdout,
+        "Ok([\"a.txt\", \"b.txt\"]) Ok(1) [\"backup\", \"c.txt\"]\n"
+    );
+    assert_eq!(
+        test_app.invoke(["cp", "-f", "a.txt", "backup"]).stdout,
+        "Ok([\"a.txt\", \"backup\"]) Err(\"No value passed\") []\n"
+    );
+    assert!(matches!(test_app.invoke(["cp", "-f", ";"]).error.as_ref().map(FliError::inner), Some(FliError::MissingValue { .. })));
+}
+
+// test the bounds on the number of values of an option
+#[test]
+pub fn test_value_count() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-p --point, <...>", "testing", |_app| {});
+    fli.value_count("--point", 2, Some(3));
+    fli.option("-r --range, [...]", "testing", |_app| {});
+    fli.value_count("-r", 2, Some(2));
+    fli.option("-t --tags, <...>", "testing", |_app| {});
+    fli.value_count("-t", 2, None);
+    fli.default(|_app| {});
+    let mut test_app = TestApp::new(fli);
+
+    assert!(test_app.invoke(["-p", "1", "2", "-r", "1", "9", "-t", "a", "b", "c"]).error.is_none());
+    assert!(test_app.invoke(Vec::<String>::new()).error.is_none());
+    let invalid = |option: &str, message: &str, index| {
+        Some(FliError::InvalidValue { option: option.to_string(), message: message.to_string(), index })
+    };
+    assert_eq!(test_app.invoke(["-p", "1"]).error, invalid("--point", "expected at least 2 values, got 1", 2));
+    assert_eq!(
+        test_app.invoke(["-t", "a", "-p", "1", "2", "3", "4"]).error,
+        invalid("--tags", "expected at least 2 values, got 1", 2)
+    );
+    assert_eq!(
+        test_app.invoke(["-p", "1", "2", "3", "4"]).error,
+        invalid("--point", "expected at most 3 values, got 4", 2)
+    );
+    assert_eq!(test_app.invoke(["-r"]).error, invalid("--range", "expected 2 values, got 0", 2));
+}
+
+// test the positions options were passed at, counted from the app
+#[test]
+pub fn test_occurrences() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.command("build", "testing")
+        .option("-I --include, <>", "testing", |_app| {})
+        .option("-L --library, <>", "testing", |_app| {})
+        .default(|_app| {})
+        .after(|app| {
+            crate::fli_println!(
+                "{:?} {:?} {} {:?}",
+                app.occurrences("include"),
+                app.occurrences("-L"),
+                app.get_count("-I"),
+                app.occurrences("missing")
+            )
+        });
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(
+        test_app.invoke(["build", "-I", "src", "-L", "lib", "--include", "vendor"]).stdout,
+        "[2, 6] [4] 2 []\n"
+    );
+    assert_eq!(test_app.invoke(["build"]).stdout, "[] [] 0 []\n");
+}
+
+// test listing the options passed along the command chain
+#[test]
+pub fn test_provided_options() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-v --verbose", "testing", |_app| {});
+    fli.option("-n --name, <>", "testing", |_app| {});
+    fli.command("deploy", "testing")
+        .option("-e --env, <...>", "testing", |_app| {})
+        .default(|_app| {})
+        .after(|app| crate::fli_println!("{:?}", app.provided_options()));
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(
+        test_app.invoke(["-v", "deploy", "-e", "prod", "eu", "--env", "us"]).stdout,
+        "[(\"--verbose\", []), (\"--env\", [\"prod\", \"eu\", \"us\"])]\n"
+    );
+    assert_eq!(test_app.invoke(["deploy"]).stdout, "[]\n");
+}
+
+// test rebuilding the command line from the parsed args
+#[test]
+pub fn test_to_command_line() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-v --verbose", "testing", |_app| {});
+    fli.command("deploy", "testing")
+        .option("-e --env, <...>", "testing", |_app| {})
+        .default(|_app| {})
+        .after(|app| crate::fli_println!("{}", app.to_command_line()));
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(
+        test_app
+            .invoke(["-v", "deploy", "my app", "-e", "prod", "eu", "--", "echo", "it's"])
+            .stdout,
+        "fli-test --verbose deploy 'my app' --env prod eu -- echo 'it'\\''s'\n"
+    );
+    assert_eq!(test_app.invoke(["deploy"]).stdout, "fli-test deploy\n");
+}
+
+// test the typed getters
+#[test]
+pub fn test_typed_getters() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-l --level, []", "testing", |_app| {});
+    fli.option("-r --ratio, []", "testing", |_app| {});
+    fli.option("-c --cache, []", "testing", |_app| {});
+    fli.option("-o --output, []", "testing", |_app| {});
+    fli.option("-p --ports, <...>", "testing", |_app| {});
+    fli.default(|_app| {});
+    fli.after(|app| {
+        crate::fli_println!(
+            "{:?} {:?} {:?} {:?} {:?}",
+            app.get_int("level".to_string()),
+            app.get_float("ratio".to_string()),
+            app.get_bool("cache".to_string()),
+            app.get_path("output".to_string()),
+            app.get_value_list::<u16>("ports".to_string())
+        );
+    });
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(
+        test_app
+            .invoke(["-l", "3", "-r", "0.5", "-c", "off", "-o", "out.txt", "-p", "80", "443"])
+            .stdout,
+        "Ok(3) Ok(0.5) Ok(false) Ok(\"out.txt\") Ok([80, 443])\n"
+    );
+    let stdout = test_app.invoke(["-l", "high", "-p", "80", "web"]).stdout;
+    assert!(stdout.starts_with("Err(\"Invalid integer for --level"));
+    assert!(stdout.ends_with("for --ports: 'web' is not a valid unsigned integer\")\n"));
+}
+
+// test the actions choosing how options behave when passed
+#[test]
+pub fn test_arg_action() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-I --include, <>", "testing", |_app| {});
+    fli.set_arg_action("-I", ArgAction::Append);
+    fli.option("-n --name, <>", "testing", |_app| {});
+    fli.set_arg_action("-n", ArgAction::Set);
+    fli.option("-v --verbose", "testing", |_app| {});
+    fli.set_arg_action("-v", ArgAction::Count);
+    fli.option("--no-color", "testing", |_app| {});
+    fli.set_arg_action("--no-color", ArgAction::SetFalse);
+    fli.option("-i --info", "testing", |_app| crate::fli_println!("callback"));
+    fli.set_arg_action("-i", ArgAction::Help);
+    fli.default(|_app| {});
+    fli.after(|app| {
+        let includes = app.get_values("include".to_string()).unwrap_or_default();
+        let name = app.get_values("name".to_string()).unwrap_or_default();
+        crate::fli_println!(
+            "{} {} {} {}",
+            includes.join(","),
+            name.join(","),
+            app.get_count("-v"),
+            app.get_flag("--no-color")
+        );
+    });
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(Vec::<String>::new()).stdout, "  0 true\n");
+    assert_eq!(
+        test_app
+            .invoke(["-I", "src", "-n", "a", "-I", "lib", "-v", "-n", "b", "-v", "--no-color"])
+            .stdout,
+        "src,lib b 2 false\n"
+    );
+    let output = test_app.invoke(["-i"]);
+    assert!(output.stdout.contains("Options:"));
+    assert!(!output.stdout.contains("callback"));
+}
+
+// test looking options up by a name that differs from their long flag
+#[test]
+pub fn test_option_name() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-H --human-readable", "testing", |_app| {});
+    fli.option("-s --size, <>", "testing", |_app| {});
+    fli.set_option_name("--human-readable", "human");
+    fli.set_option_name("-s", "block");
+    fli.default(|_app| {});
+    fli.after(|app| {
+        let size = app.get_values("block".to_string()).unwrap_or_default();
+        crate::fli_println!("{} {}", app.is_passed("human".to_string()), size.join(","));
+    });
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(["--human-readable", "-s", "4"]).stdout, "true 4\n");
+    assert_eq!(test_app.invoke(["-H"]).stdout, "true \n");
+    assert_eq!(test_app.invoke(Vec::<String>::new()).stdout, "false \n");
+}
+
+// test that the callbacks of passed options run by priority, help winning over version
+#[test]
+pub fn test_option_priority() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_version("1.0.0");
+    fli.option("-a --first", "testing", |_app| crate::fli_println!("first"));
+    fli.option("-b --second", "testing", |_app| crate::fli_println!("second"));
+    fli.set_option_priority("-b", 1);
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(["-a", "-b"]).stdout, "second\nfirst\n");
+    assert_eq!(test_app.invoke(["--version", "-a"]).stdout, "fli-test 1.0.0\n");
+    let output = test_app.invoke(["--version", "--help"]);
+    assert!(output.stdout.contains("Options:"));
+    assert!(!output.stdout.contains("fli-test 1.0.0"));
+}
+
+// test the text blocks around the help screen
+#[test]
+pub fn test_before_and_after_help() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_help_template("{name}");
+    fli.before_help("== fli-test ==").after_help("Docs: https://docs.rs/fli");
+    fli.command("greet", "testing");
+    let mut test_app = TestApp::new(fli);
+    assert_eq!(
+        test_app.invoke(["--help"]).stdout,
+        "== fli-test ==\n  Name: fli-test\nDocs: https://docs.rs/fli\n"
+    );
+    assert_eq!(test_app.invoke(["greet", "--help"]).stdout, "  Name: greet\n");
+}
+
+// test overriding the usage line of the help screen
+#[test]
+pub fn test_set_usage() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_help_template("{usage}");
+    fli.command("cp", "testing").set_usage("fli-test cp [options] <source>... <dest>");
+    let mut test_app = TestApp::new(fli);
+    assert_eq!(test_app.invoke(["--help"]).stdout, "  Usage: fli-test [options|commands]\n");
+    assert_eq!(
+        test_app.invoke(["cp", "--help"]).stdout,
+        "  Usage: fli-test cp [options] <source>... <dest>\n"
+    );
+}
+
+// test that deprecated options and commands warn but keep working
+#[test]
+pub fn test_deprecations() {
+    static OLD_RAN: AtomicBool = AtomicBool::new(false);
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-o --old", "testing", |_app| OLD_RAN.store(true, Ordering::SeqCst));
+    fli.option("-q --quiet", "testing", |_app| {});
+    fli.deprecate_option("o", "use --new instead");
+    fli.command("rm", "testing").option("-f --force", "testing", |_app| {});
+    fli.deprecate_command("rm", "use 'delete' instead");
+    let mut test_app = TestApp::new(fli);
+
+    let output = test_app.invoke(["-o"]);
+    assert!(OLD_RAN.load(Ordering::SeqCst));
+    assert!(output.stderr.contains("--old is deprecated, use --new instead"));
+    assert!(test_app.invoke(["--old", "--quiet"]).stderr.is_empty());
+    assert!(test_app.invoke(["rm", "-f"]).stderr.contains("rm is deprecated, use 'delete' instead"));
+
+    display::set_quiet(true);
+    assert!(test_app.invoke(["rm", "-f"]).stderr.is_empty());
+    display::set_quiet(false);
+}
+
+// test that hidden aliases work without being listed, warning when deprecated
+#[test]
+pub fn test_hidden_aliases() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("--dry-run", "testing", |_app| {});
+    fli.hidden_option_alias("--dry-run", "--dryrun");
+    fli.deprecate_alias("--dryrun", "use --dry-run instead");
+    fli.command("delete", "testing")
+        .default(|_app| {})
+        .after(|app| crate::fli_println!("{} {}", app.command_path().join(" "), app.is_passed("dry-run".to_string())));
+    fli.hidden_command_alias("delete", "rm");
+    fli.default(|_app| {});
+    let mut test_app = TestApp::new(fli.clone());
+
+    let output = test_app.invoke(["--dryrun", "rm"]);
+    assert_eq!(output.stdout, "fli-test delete false\n");
+    assert!(output.stderr.contains("--dryrun is deprecated, use --dry-run instead"));
+    assert!(test_app.invoke(["--dry-run"]).stderr.is_empty());
+
+    let help = fli.render_help();
+    assert!(!help.contains("dryrun"));
+    assert!(!help.contains("rm"));
+    assert_eq!(fli.complete("r"), Vec::<String>::new());
+    assert!(fli.render_help_for("rm").is_some());
+}
+
+// test that unique prefixes of long options are accepted when abbreviations are allowed
+#[test]
+pub fn test_abbreviations() {
+    static VERBOSE_RAN: AtomicBool = AtomicBool::new(false);
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.allow_abbreviations(true);
+    fli.option("--verbose", "testing", |_app| VERBOSE_RAN.store(true, Ordering::SeqCst));
+    fli.option("--verify", "testing", |_app| {});
+    fli.option("-n --name, <>", "testing", |app| {
+        assert_eq!(app.get_values("name".to_string()).unwrap(), vec!["fli"]);
+    });
+    let mut test_app = TestApp::new(fli);
+
+    let output = test_app.invoke(["--verb", "--na", "fli"]);
+    assert_eq!(output.status, 0);
+    assert!(VERBOSE_RAN.load(Ordering::SeqCst));
+
+    let output = test_app.invoke(["--ver"]);
+    assert_eq!(output.status, 2);
+    match output.error {
+        Some(FliError::AmbiguousOption { option, candidates, .. }) => {
+            assert_eq!(option, "--ver");
+            assert_eq!(candidates, vec!["--verbose", "--verify", "--version"]);
+        }
+        other => panic!("expected an ambiguous option error, got {other:?}"),
+    }
+}
+
+// test that unknown options are errors with suggestions of similar registered options
+#[test]
+pub fn test_unknown_option_suggestions() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-v --verbose", "testing", |_app| {});
+    fli.option("-n --name, <>", "testing", |_app| {});
+
+    let output = TestApp::new(fli.clone()).invoke(["--verbsoe"]);
+    assert_eq!(output.status, 2);
+    match output.error {
+        Some(FliError::UnknownOption { option, suggestions, .. }) => {
+            assert_eq!(option, "--verbsoe");
+            assert_eq!(suggestions, vec!["--verbose"]);
+        }
+        other => panic!("expected an unknown option error, got {other:?}"),
+    }
+
+    fli.set_args(vec!["fli-test".to_string(), "-x".to_string()]);
+    let (_, stdout, _) = display::capture(|| catch_exit(|| fli.run()).map(|_| ()));
+    assert!(stdout.contains("Unknown option -x"));
+    assert!(stdout.contains("Did you mean:"));
+    assert!(stdout.contains("-v"));
+}
+
+// test that a near miss with one suggestion prints the corrected command line
+#[test]
+pub fn test_fix_it_suggestions() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.command("ls", "testing")
+        .option("-s --sort, <>", "testing", |_app| {})
+        .default(|_app| {});
+
+    fli.set_args(["fli-test", "ls", "--srot", "name"].map(String::from).to_vec());
+    let (_, stdout, _) = display::capture(|| catch_exit(|| fli.run()).map(|_| ()));
+    assert!(stdout.contains("Did you mean: fli-test ls --sort name\n"));
+
+    fli.set_args(["fli-test", "lx", "my dir"].map(String::from).to_vec());
+    let (_, stdout, _) = display::capture(|| catch_exit(|| fli.run()).map(|_| ()));
+    assert!(stdout.contains("Did you mean: fli-test ls 'my dir'\n"));
+}
+
+// test that the version option prints the name and version then exits
+#[test]
+pub fn test_version_option() {
+    static GREET_RAN: AtomicBool = AtomicBool::new(false);
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_version("1.2.3");
+    fli.option("-g --greet", "testing", |_app| GREET_RAN.store(true, Ordering::SeqCst));
+
+    let mut test_app = TestApp::new(fli);
+    let output = test_app.invoke(["--version", "-g"]);
+    assert_eq!(output.status, 0);
+    assert_eq!(output.stdout, "fli-test 1.2.3\n");
+    assert!(!GREET_RAN.load(Ordering::SeqCst));
+    assert_eq!(test_app.invoke(["-V"]).stdout, "fli-test 1.2.3\n");
+    // `-v` still works but is not listed
+    assert_eq!(test_app.invoke(["-v"]).stdout, "fli-test 1.2.3\n");
+    let help = test_app.invoke(["--help"]).stdout;
+    assert!(help.contains("--version | -V "));
+    assert!(!help.contains("-v "));
+}
+
+// test that an option of the app with the short name `-v` replaces the hidden alias of `--version`
+#[test]
+pub fn test_own_v_option() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_version("1.2.3");
+    fli.option("-v --verify", "testing", |_app| crate::fli_println!("verifying"));
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(["-v"]).stdout, "verifying\n");
+    assert_eq!(test_app.invoke(["-V"]).stdout, "fli-test 1.2.3\n");
+}
+
+// test that the default command runs with all the args when no command is passed
+#[test]
+pub fn test_default_command() {
+    static SERVE_RAN: AtomicBool = AtomicBool::new(false);
+    static BUILD_RAN: AtomicBool = AtomicBool::new(false);
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.command("serve", "testing")
+        .option("-p --port, <>", "testing", |app| {
+            assert_eq!(app.get_values("port".to_string()).unwrap(), vec!["8080"]);
+            SERVE_RAN.store(true, Ordering::SeqCst);
+        });
+    fli.command("build", "testing")
+        .default(|_app| BUILD_RAN.store(true, Ordering::SeqCst));
+    fli.default_command("serve");
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(["-p", "8080"]).status, 0);
+    assert!(SERVE_RAN.load(Ordering::SeqCst));
+    assert_eq!(test_app.invoke(["build"]).status, 0);
+    assert!(BUILD_RAN.load(Ordering::SeqCst));
+    assert_eq!(test_app.invoke(["--version"]).stdout, "fli-test \n");
+}
+
+// test that the unmatched callback gets the raw args of a command that does not exist
+#[test]
+pub fn test_on_unmatched() {
+    static UNMATCHED_RAN: AtomicBool = AtomicBool::new(false);
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.command("serve", "testing").default(|_app| {});
+    fli.on_unmatched(|_app, args| {
+        assert_eq!(args, ["stash", "list", "--all"]);
+        UNMATCHED_RAN.store(true, Ordering::SeqCst);
+    });
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(["serve"]).status, 0);
+    assert!(!UNMATCHED_RAN.load(Ordering::SeqCst));
+    let output = test_app.invoke(["stash", "list", "--all"]);
+    assert!(UNMATCHED_RAN.load(Ordering::SeqCst));
+    assert!(!output.stdout.contains("Command not found"));
+}
+
+// test that a command requiring a command errors or prints help when run bare
+#[test]
+pub fn test_subcommand_required_and_arg_required_else_help() {
+    static ADD_RAN: AtomicBool = AtomicBool::new(false);
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.arg_required_else_help(true);
+    fli.command("remote", "testing")
+        .subcommand_required(true)
+        .command("add", "testing")
+        .default(|_app| ADD_RAN.store(true, Ordering::SeqCst));
+    let mut test_app = TestApp::new(fli);
+
+    let output = test_app.invoke(["remote"]);
+    assert_eq!(output.status, 2);
+    assert_eq!(
+        output.error,
+        Some(FliError::InCommand {
+            command_path: vec!["fli-test".to_string(), "remote".to_string()],
+            error: Box::new(FliError::MissingSubcommand { command: "remote".to_string() }),
+        })
+    );
+    assert_eq!(test_app.invoke(["remote", "--help"]).status, 0);
+    assert_eq!(test_app.invoke(["remote", "add"]).status, 0);
+    assert!(ADD_RAN.load(Ordering::SeqCst));
+
+    let output = test_app.invoke(Vec::<String>::new());
+    assert_eq!(output.status, 0);
+    assert!(output.stdout.contains("Options:"));
+}
+
+thread_local! {
+    static HOOK_CALLS: RefCell<Vec<&'static str>> = const { RefCell::new(vec![]) };
+}
+
+fn record_hook(name: &'static str) {
+    HOOK_CALLS.with(|calls| calls.borrow_mut().push(name));
+}
+
+// test that hooks run around the callbacks in order, with the app hooks wrapping the command ones
+#[test]
+pub fn test_hooks() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.before_each(|_app| record_hook("before_each"));
+    fli.after_each(|_app| record_hook("after_each"));
+    fli.before(|_app| record_hook("app before"));
+    fli.option("-g --greet", "testing", |_app| record_hook("greet"));
+    fli.command("deploy", "testing")
+        .before(|_app| record_hook("before"))
+        .after(|_app| record_hook("after"))
+        .default(|_app| record_hook("deploy"));
+    let mut test_app = TestApp::new(fli);
+
+    test_app.invoke(["deploy"]);
+    let calls = HOOK_CALLS.with(|calls| calls.take());
+    assert_eq!(calls, ["before_each", "before", "deploy", "after", "after_each"]);
+
+    test_app.invoke(["-g"]);
+    let calls = HOOK_CALLS.with(|calls| calls.take());
+    assert_eq!(calls, ["before_each", "app before", "greet", "after_each"]);
+
+    // the after hooks still run when the callbacks are stopped
+    assert_eq!(test_app.invoke(["deploy", "--help"]).status, 0);
+    let calls = HOOK_CALLS.with(|calls| calls.take());
+    assert_eq!(calls, ["before_each", "before", "after", "after_each"]);
+
+    test_app.invoke(["--name"]);
+    assert!(HOOK_CALLS.with(|calls| calls.take()).is_empty());
+}
+
+// test that validators run before the hooks and callbacks and report their error
+#[test]
+pub fn test_validate() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.command("show", "testing")
+        .option("-n --name, <>", "testing", |_app| {})
+        .option("-i --id, <>", "testing", |_app| record_hook("show"))
+        .before(|_app| record_hook("before"))
+        .validate(|app| {
+            if app.is_passed("--name".to_string()) == app.is_passed("--id".to_string()) {
+                return Err("pass either --name or --id".to_string());
+            }
+            Ok(())
+        });
+    let mut test_app = TestApp::new(fli);
+
+    let output = test_app.invoke(["show", "-n", "james", "-i", "1"]);
+    assert_eq!(
+        output.error.as_ref().map(FliError::inner),
+        Some(&FliError::ValidationFailed {
+            command: "show".to_string(),
+            message: "pass either --name or --id".to_string()
+        })
+    );
+    assert!(HOOK_CALLS.with(|calls| calls.take()).is_empty());
+
+    assert_eq!(test_app.invoke(["show", "--help"]).status, 0);
+    HOOK_CALLS.with(|calls| calls.take());
+    assert!(test_app.invoke(["show", "-i", "1"]).error.is_none());
+    let calls = HOOK_CALLS.with(|calls| calls.take());
+    assert_eq!(calls, ["before", "show"]);
+}
+
+// test that run hands errors to the error handler and exits with its code
+#[test]
+pub fn test_on_error() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.on_error(|error| {
+        crate::fli_eprintln!("fli-test: {error}");
+        3
+    });
+    fli.command("greet", "testing")
+        .option("-n --name, <>", "testing", |_app| {});
+
+    fli.set_args(["fli-test", "greet", "--name"].map(String::from).to_vec());
+    let (status, stdout, stderr) = display::capture(|| catch_exit(|| fli.run()).map(|_| ()));
+    assert_eq!(status, Err(3));
+    assert!(stdout.is_empty());
+    assert_eq!(stderr, "fli-test: Invalid syntax : --name does not have a value\n");
+}
+
+// test replacing how errors are shown with an error renderer
+#[test]
+pub fn test_error_renderer() {
+    struct OneLine;
+    impl ErrorRenderer for OneLine {
+        fn render(&self, app: &Fli, error: &FliError) -> String {
+            format!("{}: {error}\n", app.get_app_name())
+        }
+    }
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_error_renderer(Box::new(OneLine));
+    fli.set_exit_code(FliErrorKind::MissingValue, 4);
+    fli.command("greet", "testing")
+        .option("-n --name, <>", "testing", |_app| {});
+
+    fli.set_args(["fli-test", "greet", "--name"].map(String::from).to_vec());
+    let (status, stdout, _) = display::capture(|| catch_exit(|| fli.run()).map(|_| ()));
+    assert_eq!(status, Err(4));
+    assert_eq!(stdout, "greet: Invalid syntax : --name does not have a value\n");
+    let error = FliError::UnknownOption { option: "--nme".to_string(), suggestions: vec!["--name".to_string()], index: 1 };
+    let rendered = DefaultErrorRenderer.render(&fli, &error);
+    assert!(rendered.contains("Unknown option --nme"));
+    assert!(rendered.contains("Did you mean:"));
+    assert!(rendered.contains("Options:"));
+}
+
+// test that errors in commands name the command path
+#[test]
+pub fn test_error_command_path() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-v --verbose", "testing", |_app| {});
+    fli.command("remote", "testing").command("add", "testing").default(|_app| {});
+
+    fli.set_args(["fli-test", "remote", "add", "-x"].map(String::from).to_vec());
+    let (status, stdout, _) = display::capture(|| catch_exit(|| fli.run()).map(|_| ()));
+    assert_eq!(status, Err(2));
+    assert!(stdout.contains("error in 'fli-test remote add': Unknown option -x"));
+    fli.set_args(["fli-test", "-x"].map(String::from).to_vec());
+    let (_, stdout, _) = display::capture(|| catch_exit(|| fli.run()).map(|_| ()));
+    assert!(stdout.contains("Unknown option -x") && !stdout.contains("error in"));
+}
+
+// test that run exits with the exit code of the kind of error
+#[test]
+pub fn test_exit_codes() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_exit_code(FliErrorKind::UnknownOption, 64);
+    fli.command("greet", "testing")
+        .option("-n --name, <>", "testing", |_app| {});
+
+    let mut run = |args: &[&str]| {
+        fli.set_args(args.iter().map(|arg| arg.to_string()).collect());
+        display::capture(|| catch_exit(|| fli.run()).map(|_| ())).0
+    };
+    assert_eq!(run(&["fli-test", "greet", "--name"]), Err(2));
+    assert_eq!(run(&["fli-test", "greet", "--nmae"]), Err(64));
+    assert_eq!(run(&["fli-test", "--help"]), Err(0));
+
+    // `TestApp` reports the code `run` would exit with
+    let mut test_app = TestApp::new(fli.clone());
+    assert_eq!(test_app.invoke(["greet", "--nmae"]).status, 64);
+    fli.use_sysexits();
+    fli.command("check", "testing").validate(|_app| Err("not ready".to_string()));
+    let mut test_app = TestApp::new(fli.clone());
+    assert_eq!(test_app.invoke(["greet", "--name"]).status, 64);
+    assert_eq!(test_app.invoke(["check"]).status, 65);
+    assert_eq!(FliErrorKind::InvalidValue.sysexits_code(), 65);
+    fli.on_error(|_error| 9);
+    let output = TestApp::new(fli).invoke(["greet", "--name"]);
+    assert_eq!((output.status, output.error.is_some()), (9, true));
+}
+
+// test that errors point at the arg they are about, in the whole command line
+#[test]
+pub fn test_error_spans() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.command("ls", "testing")
+        .option("-s --sort, <>", "testing", |_app| {});
+    let mut test_app = TestApp::new(fli.clone());
+
+    let error = test_app.invoke(["ls", "--sot", "name"]).error.unwrap();
+    assert_eq!(error.index(), Some(2));
+    let error = test_app.invoke(["ls", "-s"]).error.unwrap();
+    assert_eq!(error.index(), Some(2));
+    let error = test_app.invoke(["--color", "pink"]).error.unwrap();
+    assert_eq!(error.index(), Some(2));
+
+    fli.set_args(["fli-test", "ls", "--sot", "name"].map(String::from).to_vec());
+    let (_, stdout, _) = display::capture(|| catch_exit(|| fli.run()).map(|_| ()));
+    assert!(stdout.contains("fli-test ls --sot name"));
+    assert!(stdout.contains("            ^^^^^"));
+}
+
+// test that unknown options are collected instead of erroring when allowed
+#[test]
+pub fn test_unknown_args() {
+    static GREET_RAN: AtomicBool = AtomicBool::new(false);
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.allow_unknown_args(true);
+    fli.option("-g --greet", "testing", |app| {
+        assert_eq!(app.unknown_args(), vec!["--jobs=4", "-x"]);
+        GREET_RAN.store(true, Ordering::SeqCst);
+    });
+
+    let output = TestApp::new(fli).invoke(["--jobs=4", "-g", "-x"]);
+    assert_eq!(output.status, 0);
+    assert!(GREET_RAN.load(Ordering::SeqCst));
+}
+
+// test that @file args are replaced by the args in the file
+#[test]
+pub fn test_response_files() {
+    let dir = std::env::temp_dir().join(format!("fli-response-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let inner = dir.join("inner.txt");
+    std::fs::write(&inner, "-n 'james bond'\n").unwrap();
+    let outer = dir.join("outer.txt");
+    std::fs::write(&outer, format!("greet\n@{}\n", inner.display())).unwrap();
+    let looping = dir.join("loop.txt");
+    std::fs::write(&looping, format!("@{}", looping.display())).unwrap();
+
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.allow_response_files(true);
+    fli.command("greet", "testing")
+        .option("-n --name, <>", "testing", |app| {
+            let name = app.get_values("--name".to_string()).unwrap();
+            crate::fli_println!("Hello {}", name[0]);
+        });
+    let mut test_app = TestApp::new(fli);
+
+    let output = test_app.invoke([format!("@{}", outer.display())]);
+    assert_eq!(output.stdout, "Hello james bond\n");
+    let output = test_app.invoke([format!("@{}", looping.display())]);
+    assert!(matches!(output.error, Some(FliError::InvalidValue { index: 1, .. })));
+    let output = test_app.invoke(["@missing-fli-file.txt"]);
+    assert!(matches!(output.error, Some(FliError::InvalidValue { message, .. }) if message.starts_with("can not read")));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+// test that each command picks what to do with unknown options
+#[test]
+pub fn test_unknown_option_policy() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.command("exec", "testing")
+        .set_unknown_option_policy(UnknownOptionPolicy::Warn)
+        .default(|app| {
+            assert!(app.unknown_args().is_empty());
+            crate::fli_println!("ran");
+        });
+    fli.command("forward", "testing")
+        .set_unknown_option_policy(UnknownOptionPolicy::Collect)
+        .default(|app| crate::fli_println!("{:?}", app.unknown_args()));
+    let mut test_app = TestApp::new(fli);
+
+    let output = test_app.invoke(["exec", "-x"]);
+    assert!(output.error.is_none());
+    assert_eq!(output.stdout, "ran\n");
+    assert!(output.stderr.contains("ignoring unknown option -x"));
+
+    let output = test_app.invoke(["forward", "-x", "--jobs=4"]);
+    assert_eq!(output.stdout, "[\"-x\", \"--jobs=4\"]\n");
+    assert!(output.stderr.is_empty());
+
+    let output = test_app.invoke(["-x"]);
+    assert!(matches!(output.error, Some(FliError::UnknownOption { .. })));
+}
+
+// test that prompts read answers, ask again on invalid ones and follow --yes and --no-input
+#[test]
+pub fn test_prompts() {
+    prompt::set_input(Box::new(std::io::Cursor::new("maybe\ny\n\nabc\n42\n7\n2\n s3cret \n")));
+    let (_, stdout, _) = display::capture(|| {
+        assert!(prompt::confirm("Remove 'x'?", false));
+        assert!(prompt::confirm("Keep going?", true));
+        assert_eq!(prompt::input::<u16>("Port"), Ok(42));
+        assert_eq!(prompt::select("Shell", &["bash", "zsh"]), Ok(1));
+        assert_eq!(prompt::password("Token"), Ok("s3cret".to_string()));
+        assert!(prompt::input::<u16>("Port").is_err());
+    });
+    prompt::reset_input();
+    assert!(stdout.contains("Remove 'x'? [y/N] Please answer yes or no"));
+    assert!(stdout.contains("'abc' is not a valid unsigned integer"));
+    assert!(stdout.contains("'7' is not one of the numbers"));
+
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-y --yes", "testing", |_app| {});
+    fli.option("--no-input", "testing", |_app| {});
+    fli.option("-r --remove", "testing", |app| {
+        let no_input = app.is_passed("--no-input".to_string());
+        assert_eq!(prompt::confirm("Remove?", false), !no_input);
+        assert_eq!(prompt::input::<String>("Name").is_err(), no_input);
+    });
+    let mut test_app = TestApp::new(fli);
+    prompt::set_input(Box::new(std::io::Cursor::new("fli\n")));
+    assert_eq!(test_app.invoke(["-r", "--yes"]).status, 0);
+    assert_eq!(test_app.invoke(["-r", "--no-input"]).status, 0);
+    prompt::reset_input();
+}
+
+// test that lines are split into words like a shell would
+#[test]
+pub fn test_split_shell_words() {
+    assert_eq!(split_shell_words("greet -n  james").unwrap(), vec!["greet", "-n", "james"]);
+    assert_eq!(
+        split_shell_words(r#"say "hello \"fli\"" 'it\s' a\ b ''"#).unwrap(),
+        vec!["say", "hello \"fli\"", "it\\s", "a b", ""]
+    );
+    assert!(split_shell_words("say 'hello").is_err());
+}
+
+// test that the repl runs each line like args and keeps going after errors and exits
+#[test]
+pub fn test_repl() {
+    static GREETED: AtomicUsize = AtomicUsize::new(0);
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.command("greet", "testing").option("-n --name, <>", "testing", |app| {
+        assert_eq!(app.get_values("name".to_string()).unwrap(), vec!["fli rs"]);
+        GREETED.fetch_add(1, Ordering::SeqCst);
+    });
+
+    prompt::set_input(Box::new(std::io::Cursor::new(
+        "greet -n 'fli rs'\n\n!!\n!1\n!9\ngreet --nmae\n--help\nhistory\nexit\ngreet -n never\n",
+    )));
+    let (_, stdout, stderr) = display::capture(|| fli.repl());
+    prompt::reset_input();
+    assert_eq!(GREETED.load(Ordering::SeqCst), 3);
+    assert!(stdout.contains("fli-test> greet -n 'fli rs'\n"));
+    assert!(stderr.contains("No line 9 in the history"));
+    assert!(stdout.contains("Unknown option --nmae"));
+    assert!(stdout.contains("Options:"));
+    assert!(stdout.contains(
+        "   1  greet -n 'fli rs'\n   2  greet -n 'fli rs'\n   3  greet -n 'fli rs'\n   4  greet --nmae\n   5  --help\n"
+    ));
+    assert!(stdout.ends_with("fli-test> "));
+}
+
+// test that the last word of a line is completed from the commands and options
+#[test]
+pub fn test_complete() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.command("remote", "testing")
+        .option("-v --verbose", "testing", |_app| {})
+        .command("add", "testing");
+    fli.command("rename", "testing");
+
+    assert_eq!(fli.complete("re"), vec!["remote", "rename"]);
+    assert_eq!(fli.complete("remote "), vec!["add"]);
+    assert_eq!(fli.complete("remote --ve"), vec!["--verbose"]);
+    assert_eq!(fli.complete("remote -"), vec!["--color", "--help", "--verbose", "-h", "-v"]);
+}
+
+// test that the progress bar renders its position and stays hidden when not on a terminal
+#[test]
+pub fn test_progress_bar() {
+    let (_, _, stderr) = display::capture(|| {
+        let mut progress = ProgressBar::new(4);
+        assert!(progress.render().starts_with("[----------"));
+        assert!(progress.render().ends_with("] 0/4 (0%)"));
+        progress.inc(1);
+        assert!(progress.render().contains("] 1/4 (25%) "));
+        assert!(progress.render().contains(" ETA "));
+        progress.set_position(10);
+        assert_eq!(progress.position(), 4);
+        assert!(progress.render().starts_with("[##########"));
+        assert!(!progress.render().contains("ETA"));
+        progress.finish_with_message("done");
+    });
+    assert!(stderr.is_empty());
+}
+
+// test that --quiet hides non essential output while the callbacks run
+#[test]
+pub fn test_quiet_option() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-q --quiet", "testing", |_app| {});
+    fli.option("-c --copy", "testing", |app| {
+        assert_eq!(display::is_quiet(), app.is_passed("--quiet".to_string()));
+    });
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(["-c", "-q"]).status, 0);
+    assert_eq!(test_app.invoke(["-c"]).status, 0);
+    assert!(!display::is_quiet());
+}
+
+// test that the spinner is hidden and silent when not on a terminal
+#[test]
+pub fn test_spinner_hidden() {
+    let (_, _, stderr) = display::capture(|| {
+        let spinner = Spinner::start("scanning");
+        assert!(spinner.is_hidden());
+        spinner.message("still scanning");
+        spinner.finish_with_message("done");
+    });
+    assert!(stderr.is_empty());
+}
+
+// test that tables line up wide characters, align columns and wrap or truncate long cells
+#[test]
+pub fn test_table() {
+    display::set_ascii_output(false);
+    let mut table = Table::new(["Name", "Size", "Note"]);
+    table
+        .align(1, Align::Right)
+        .align(2, Align::Center)
+        .max_width(0, 6)
+        .overflow(0, Overflow::Truncate)
+        .max_width(2, 5)
+        .row_separators(true)
+        .add_row(["日本語", "1", "ok"])
+        .add_row(["abcdefgh", "200", "a b c d e f"]);
+    assert_eq!(
+        table.render(),
+        [
+            "Name   | Size | Note",
+            "日本語 |    1 |  ok",
+            "-------+------+------",
+            "abcde… |  200 | a b c",
+            "       |      | d e f",
+            "",
+        ]
+        .join("\n")
+    );
+    assert_eq!(wrap_text("日本語 テキスト", 6), vec!["日本語", "テキス", "ト"]);
+}
+
+// test the ascii, markdown and borderless table styles
+#[test]
+pub fn test_table_styles() {
+    let mut table = Table::new(["Name", "Size"]);
+    table.align(1, Align::Right).add_row(["a|b", "1"]).add_row(["cc", "22"]);
+
+    table.style(TableStyle::Ascii);
+    assert_eq!(
+        table.render(),
+        "+------+------+\n| Name | Size |\n+------+------+\n| a|b  |    1 |\n| cc   |   22 |\n+------+------+\n"
+    );
+    table.style(TableStyle::Markdown);
+    assert_eq!(
+        table.render(),
+        "| Name | Size |\n| ---- | ---: |\n| a\\|b |    1 |\n| cc   |   22 |\n"
+    );
+    table.style(TableStyle::Borderless);
+    assert_eq!(table.render(), "Name  Size\na|b      1\ncc      22\n");
+}
+
+// test that the theme of the app is used while it runs
+#[test]
+pub fn test_theme() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_theme(Theme {
+        header: display::Color::Magenta,
+        ..Theme::default()
+    });
+    fli.option("-c --copy", "testing", |_app| {
+        assert_eq!(display::theme().header, display::Color::Magenta);
+        display::print_success("copied");
+    });
+    fli.ascii_output(false);
+    let output = TestApp::new(fli).invoke(["-c"]);
+
+    assert_eq!(output.status, 0);
+    assert_eq!(output.stdout, "✓ copied\n");
+    display::set_theme(Theme::default());
+}
+
+// test that the messages of a locale translate the help screen and errors
+#[test]
+pub fn test_messages() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_locale("fr_FR.UTF-8");
+    fli.option("-n --name, <>", "testing", |_app| {});
+    fli.command("start", "testing");
+    let mut test_app = TestApp::new(fli);
+
+    let help = test_app.invoke(["--help"]).stdout;
+    assert!(help.contains("Utilisation"));
+    assert!(help.contains("Commandes:"));
+    assert!(help.contains("Requis"));
+    assert!(help.contains("affiche l'aide de fli-test"));
+    let output = test_app.invoke(["--nom"]);
+    assert_eq!(output.error.unwrap().to_string(), "Option inconnue --nom");
+    messages::set_messages(Messages {
+        unknown_option: "No such flag {option}".to_string(),
+        ..Messages::default()
+    });
+    assert_eq!(
+        FliError::UnknownOption { option: "--x".to_string(), suggestions: vec![], index: 1 }.to_string(),
+        "No such flag --x"
+    );
+    messages::set_messages(Messages::default());
+}
+
+// test that ascii output replaces the symbols of the theme
+#[test]
+pub fn test_ascii_output() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.ascii_output(true);
+    fli.option("-c --copy", "testing", |_app| {
+        display::print_success("copied");
+        let mut table = Table::new(["Name"]);
+        table.max_width(0, 5).overflow(0, Overflow::Truncate).add_row(["abcdefgh"]);
+        display::print(format_args!("{}", table.render()));
+    });
+    let output = TestApp::new(fli).invoke(["-c"]);
+
+    assert_eq!(output.stdout, "+ copied\nName\nab...\n");
+    assert!(display::is_ascii_output());
+    display::set_ascii_output(false);
+    assert_eq!(display::theme().symbols, display::Symbols::unicode());
+}
+
+// test that the verbosity options set the verbosity while the callbacks run
+#[test]
+pub fn test_verbosity_options() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-v --verbose", "testing", |_app| {});
+    fli.option("-d --debug", "testing", |_app| {});
+    fli.option("-q --quiet", "testing", |_app| {});
+    fli.option("-c --copy", "testing", |_app| {
+        crate::fli_debug!("verbosity {}", display::verbosity());
+    });
+    let mut test_app = TestApp::new(fli);
+    #[cfg(feature = "log")]
+    let _ = display::init_logger();
+
+    assert_eq!(test_app.invoke(["-c", "-v"]).stderr, "");
+    assert_eq!(test_app.invoke(["-c", "-v", "--verbose"]).stderr, "debug: verbosity 2\n");
+    assert_eq!(test_app.invoke(["-c", "-d", "-v", "-v", "-v"]).stderr, "debug: verbosity 3\n");
+    assert_eq!(test_app.invoke(["-c", "-d", "-q"]).stderr, "");
+    #[cfg(feature = "log")]
+    {
+        assert_eq!(display::log_level_filter(-1), log::LevelFilter::Error);
+        assert_eq!(display::log_level_filter(3), log::LevelFilter::Trace);
+        // the logger reads the verbosity of the thread, the global level is left alone
+        assert_eq!(log::max_level(), log::LevelFilter::Trace);
+    }
+    assert_eq!(display::verbosity(), 0);
+}
+
+// test the verbosity flags of the app and its commands
+#[test]
+pub fn test_add_verbosity_flags() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_version("1.0.0");
+    fli.command("build", "testing").default(|app| {
+        display::print_info(&format!("verbosity {}", app.verbosity()));
+    });
+    fli.add_verbosity_flags();
+    fli.command("test", "testing").default(|app| {
+        display::print_info(&format!("verbosity {}", app.verbosity()));
+    });
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(["-V"]).stdout, "fli-test 1.0.0\n");
+    assert_eq!(test_app.invoke(["-v", "-v", "build", "-q"]).stdout, "ℹ verbosity 1\n");
+    assert_eq!(test_app.invoke(["test", "--verbose"]).stdout, "ℹ verbosity 1\n");
+    assert_eq!(test_app.invoke(["-q", "test"]).stdout, "");
+}
+
+// test reading option values from a config file, the command line taking precedence
+#[test]
+pub fn test_enable_config_file() {
+    let dir = std::env::temp_dir().join(format!("fli-config-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let json = dir.join("app.json");
+    std::fs::write(&json, r#"{"name": "james", "verbose": true, "serve": {"port": 8080, "hosts": ["a", "b"]}}"#).unwrap();
+    let toml = dir.join("app.toml");
+    std::fs::write(&toml, "name = \"ada\"\n").unwrap();
+
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.enable_config_file(json.to_str().unwrap());
+    fli.option("-n --name, <>", "testing", |_app| {});
+    fli.option("--verbose", "testing", |_app| {});
+    fli.option("-p --print", "testing", |app| {
+        let name: String = app.get_value("name".to_string()).unwrap();
+        crate::fli_println!("{name} {}", app.is_passed("--verbose".to_string()));
+    });
+    fli.command("serve", "testing")
+        .option("-p --port, <>", "testing", |_app| {})
+        .option("--hosts, <...>", "testing", |_app| {})
+        .option("-s --show", "testing", |app| {
+            let port: u16 = app.get_value("port".to_string()).unwrap();
+            crate::fli_println!("{port} {:?}", app.get_values("hosts".to_string()).unwrap());
+        });
+    let mut test_app = TestApp::new(fli);
+
+    let error = test_app.invoke(["--config", dir.join("missing.json").to_str().unwrap()]).error;
+    assert!(matches!(error, Some(FliError::InvalidValue { ref option, .. }) if option == "--config"));
+    #[cfg(feature = "json")]
+    {
+        assert_eq!(test_app.invoke(["-p"]).stdout, "james true\n");
+        assert_eq!(test_app.invoke(["-p", "-n", "ada"]).stdout, "ada true\n");
+        assert_eq!(test_app.invoke(["serve", "-s"]).stdout, "8080 [\"a\", \"b\"]\n");
+        assert_eq!(test_app.invoke(["serve", "-s", "-p", "80"]).stdout, "80 [\"a\", \"b\"]\n");
+    }
+    #[cfg(not(feature = "json"))]
+    assert!(test_app.invoke(["-p"]).error.is_some());
+    let toml_output = test_app.invoke(["-p", "--config", toml.to_str().unwrap()]);
+    #[cfg(feature = "toml")]
+    assert_eq!(toml_output.stdout, "ada false\n");
+    #[cfg(not(feature = "toml"))]
+    assert!(matches!(toml_output.error, Some(FliError::InvalidValue { message, .. }) if message.contains("`toml` feature")));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+// test that the .env file is loaded before the callbacks run
+#[cfg(feature = "dotenv")]
+#[test]
+pub fn test_load_dotenv() {
+    let path = std::env::temp_dir().join(format!("fli-dotenv-test-{}.env", std::process::id()));
+    std::fs::write(&path, "FLI_DOTENV_TEST=from-file\n").unwrap();
+
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.load_dotenv_from(path.to_str().unwrap());
+    fli.option("-p --print", "testing", |_app| {
+        crate::fli_println!("{}", std::env::var("FLI_DOTENV_TEST").unwrap_or_default());
+    });
+
+    assert_eq!(TestApp::new(fli).invoke(["-p"]).stdout, "from-file\n");
+    std::fs::remove_file(path).unwrap();
+}
+
+// test the conventional directories of the app
+#[test]
+pub fn test_paths() {
+    let fli = Fli::init("fli-test", "cook");
+    if cfg!(windows) || cfg!(target_os = "macos") || std::env::var_os("HOME").is_none() {
+        return;
+    }
+    let home = std::path::PathBuf::from(std::env::var_os("HOME").unwrap());
+    let xdg = |name: &str, default: &str| {
+        std::env::var_os(name)
+            .map(std::path::PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .unwrap_or_else(|| home.join(default))
+            .join("fli-test")
+    };
+    assert_eq!(fli.config_dir(), Some(xdg("XDG_CONFIG_HOME", ".config")));
+    assert_eq!(fli.cache_dir(), Some(xdg("XDG_CACHE_HOME", ".cache")));
+    assert_eq!(fli.data_dir(), Some(xdg("XDG_DATA_HOME", ".local/share")));
+}
+
+// test converting a clap command
+#[cfg(feature = "clap")]
+#[test]
+pub fn test_from_clap() {
+    let command = clap::Command::new("myapp")
+        .version("1.2.0")
+        .arg(clap::Arg::new("name").short('n').long("name").required(true))
+        .arg(clap::Arg::new("verbose").long("verbose").action(clap::ArgAction::SetTrue))
+        .arg(clap::Arg::new("tags").long("tags").num_args(0..))
+        .subcommand(clap::Command::new("greet").arg(clap::Arg::new("loud").short('l').action(clap::ArgAction::SetTrue)));
+    let mut fli = crate::compat::clap::from_clap(&command);
+    fli.option("-p --print", "testing", |app| {
+        crate::fli_println!("{:?} {:?}", app.get_values("name".to_string()), app.get_values("tags".to_string()));
+    });
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(["--version"]).stdout, "myapp 1.2.0\n");
+    assert_eq!(test_app.invoke(["-p", "-n", "james", "--verbose", "--tags", "a", "b"]).stdout, "Ok([\"james\"]) Ok([\"a\", \"b\"])\n");
+    assert!(matches!(test_app.invoke(["-n"]).error.as_ref().map(FliError::inner), Some(FliError::MissingValue { .. })));
+    assert_eq!(test_app.invoke(["--tags"]).status, 0);
+    assert_eq!(test_app.invoke(["greet", "-l"]).status, 0);
+    assert!(matches!(test_app.invoke(["greet", "-x"]).error.as_ref().map(FliError::inner), Some(FliError::UnknownOption { .. })));
+}
+
+// test that categorized commands are listed under their own header
+#[test]
+pub fn test_command_categories() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_help_template("{subcommands}");
+    fli.command("init", "Create a project");
+    fli.command("cp", "Copy files").category("File operations");
+    fli.command("login", "Log in").category("Account");
+    let stdout = TestApp::new(fli).invoke(["--help"]).stdout;
+
+    let position = |text: &str| stdout.find(text).unwrap();
+    assert!(position("Commands:") < position("init"));
+    assert!(position("init") < position("File operations:"));
+    assert!(position("File operations:") < position("cp"));
+    assert!(position("cp") < position("Account:"));
+    assert!(position("Account:") < position("login"));
+}
+
+// test that grouped options are listed under their own header after the other options
+#[test]
+pub fn test_option_groups() {
+    let mut fli = Fli::init("fli-test", "ls");
+    fli.set_help_template("{options}");
+    fli.option("-l --long", "Use a long listing format", |_app| {});
+    fli.option("-a --all", "Do not ignore entries starting with .", |_app| {});
+    fli.option("-s --size", "Print the size of each file", |_app| {});
+    fli.add_option_to_group("Output options", "--long")
+        .add_option_to_group("Filtering", "a")
+        .add_option_to_group("Output options", "-s");
+    let stdout = TestApp::new(fli).invoke(["--help"]).stdout;
+
+    let position = |text: &str| stdout.find(text).unwrap();
+    assert!(position("Options:") < position("--help"));
+    assert!(position("--help") < position("Output options:"));
+    assert!(position("Output options:") < position("--long"));
+    assert!(position("--long") < position("--size"));
+    assert!(position("--size") < position("Filtering:"));
+    assert!(position("Filtering:") < position("--all"));
+}
+
+// test that a computed default is only used when the option is not passed
+#[test]
+pub fn test_default_with() {
+    let mut fli = Fli::init("fli-test", "make");
+    fli.option("-j --jobs, <>", "The number of jobs", |_app| {});
+    fli.option("-s --slow", "Run one job at a time", |app| {
+        let jobs = app.get_values("--jobs".to_string()).unwrap();
+        crate::fli_println!("jobs {}", jobs[0]);
+    });
+    fli.default_with("jobs", |app| {
+        match app.is_passed("--slow".to_string()) {
+            true => "1".to_string(),
+            false => "8".to_string(),
+        }
+    });
+    fli.default(|app| {
+        let jobs = app.get_values("--jobs".to_string()).unwrap();
+        crate::fli_println!("jobs {}", jobs[0]);
+    });
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(Vec::<String>::new()).stdout, "jobs 8\n");
+    assert_eq!(test_app.invoke(["--slow"]).stdout, "jobs 1\n");
+    assert_eq!(test_app.invoke(["--slow", "-j", "3"]).stdout, "jobs 3\n");
+}
+
+// test that the help screen lists entries in declaration or alphabetical order
+#[test]
+pub fn test_help_order() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_help_template("{options}\n{subcommands}");
+    fli.option("-z --zebra", "testing", |_app| {});
+    fli.option("-a --apple", "testing", |_app| {});
+    fli.command("zip", "testing");
+    fli.command("add", "testing");
+    let mut test_app = TestApp::new(fli.clone());
+    let stdout = test_app.invoke(["--help"]).stdout;
+    let position = |stdout: &str, text: &str| stdout.find(text).unwrap();
+
+    assert!(position(&stdout, "--help") < position(&stdout, "--version"));
+    assert!(position(&stdout, "--version") < position(&stdout, "--zebra"));
+    assert!(position(&stdout, "--zebra") < position(&stdout, "--apple"));
+    assert!(position(&stdout, "zip ") < position(&stdout, "add "));
+    assert_eq!(test_app.invoke(["--help"]).stdout, stdout);
+
+    fli.set_help_order(HelpOrder::Alphabetical);
+    let stdout = TestApp::new(fli).invoke(["--help"]).stdout;
+    assert!(position(&stdout, "--apple") < position(&stdout, "--color"));
+    assert!(position(&stdout, "--help") < position(&stdout, "--version"));
+    assert!(position(&stdout, "--version") < position(&stdout, "--zebra"));
+    assert!(position(&stdout, "add ") < position(&stdout, "zip "));
+}
+
+// test reading the options passed to the parents of a command
+#[test]
+pub fn test_get_global() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-n --name, <>", "testing", |_app| {});
+    fli.option("-l --loud", "testing", |_app| {});
+    fli.command("remote", "testing").command("add", "testing").default(|app| {
+        crate::fli_println!(
+            "{:?} {}",
+            app.get_global("name".to_string()),
+            app.is_passed_global("loud".to_string())
+        );
+    });
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(["-n", "james", "-l", "remote", "add"]).stdout, "Ok([\"james\"]) true\n");
+    assert_eq!(test_app.invoke(["remote", "add"]).stdout, "Err(\"No value passed\") false\n");
+}
+
+// test the command path and parents of a command
+#[test]
+pub fn test_command_path() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-n --name, <>", "testing", |_app| {});
+    fli.command("remote", "testing").command("add", "testing").default(|app| {
+        let root = app.parent().and_then(|remote| remote.parent()).unwrap();
+        crate::fli_println!("{} {:?}", app.command_path().join(" "), root.get_values("name".to_string()));
+    });
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(["-n", "james", "remote", "add"]).stdout, "fli-test remote add Ok([\"james\"])\n");
+    assert_eq!(test_app.app().command_path(), vec!["fli-test"]);
+}
+
+// test removing and overriding options
+#[test]
+pub fn test_remove_and_override_option() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-c --count, <>", "testing", |_app| {});
+    fli.command("serve", "testing")
+        .remove_option("color")
+        .option("-c --count, <>", "testing", |_app| {})
+        .override_option("-C --count", "testing", |_app| crate::fli_println!("flag"));
+    let mut test_app = TestApp::new(fli);
+
+    assert!(matches!(test_app.invoke(["serve", "--color", "never"]).error.as_ref().map(FliError::inner), Some(FliError::UnknownOption { .. })));
+    assert!(matches!(test_app.invoke(["serve", "-c"]).error.as_ref().map(FliError::inner), Some(FliError::UnknownOption { .. })));
+    assert_eq!(test_app.invoke(["serve", "-C"]).stdout, "flag\n");
+    assert_eq!(test_app.invoke(["serve", "--count"]).stdout, "flag\n");
+    assert!(matches!(test_app.invoke(["--count"]).error.as_ref().map(FliError::inner), Some(FliError::MissingValue { .. })));
+    let help = test_app.invoke(["serve", "--help"]).stdout;
+    assert!(!help.contains("--color") && !help.contains("Required"));
+}
+
+// test that inheritable options and their callbacks are copied to commands
+#[test]
+pub fn test_mark_inheritable() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.option("-d --debug", "testing", |app| crate::fli_println!("debug in {}", app.get_app_name()));
+    fli.command("start", "testing");
+    fli.mark_inheritable("debug");
+    fli.command("stop", "testing").command("now", "testing");
+    fli.command("own", "testing").option("--debug, <>", "testing", |_app| {});
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(["start", "--debug"]).stdout, "debug in start\n");
+    assert_eq!(test_app.invoke(["stop", "now", "-d"]).stdout, "debug in now\n");
+    assert!(matches!(test_app.invoke(["own", "--debug"]).error.as_ref().map(FliError::inner), Some(FliError::MissingValue { .. })));
+    assert!(matches!(test_app.invoke(["own", "-d"]).error.as_ref().map(FliError::inner), Some(FliError::UnknownOption { .. })));
+}
+
+// test marking all options inheritable and global options
+#[test]
+pub fn test_mark_all_inheritable() {
+    let mut fli = Fli::init("fli-test", "cook");
+    fli.set_version("1.0.0");
+    fli.command("start", "testing");
+    fli.option("-n --name, <>", "testing", |app| crate::fli_println!("name in {}", app.get_app_name()));
+    fli.mark_all_inheritable();
+    fli.option("-l --loud", "testing", |app| crate::fli_println!("loud in {}", app.get_app_name()));
+    fli.command("stop", "testing");
+    let mut other = Fli::init("fli-test", "cook");
+    other.command("start", "testing");
+    other.add_global_option("-q --quick", "testing", |app| crate::fli_println!("quick in {}", app.get_app_name()));
+    let mut test_app = TestApp::new(fli);
+
+    assert_eq!(test_app.invoke(["start", "-n", "a", "-l"]).stdout, "name in start\nloud in start\n");
+    assert_eq!(test_app.invoke(["stop", "--loud"]).stdout, "loud in stop\n");
+    assert!(matches!(test_app.invoke(["stop", "--version"]).error.as_ref().map(FliError::inner), Some(FliError::UnknownOption { .. })));
+    assert_eq!(TestApp::new(other).invoke(["start", "-q"]).stdout, "quick in start\n");
+}
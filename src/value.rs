@@ -0,0 +1,175 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::PathBuf,
+    time::Duration,
+};
+
+use crate::parse_duration;
+
+/// A type an option value can be parsed into with `Fli::get_value`
+///
+/// Implement it for your own domain types (ids, versions, dates) to read them straight from the command line
+///
+/// # Example
+/// ```
+/// use fli::FliValue;
+///
+/// struct Port(u16);
+///
+/// impl FliValue for Port {
+///     fn parse(value: &str) -> Result<Self, String> {
+///         match value.parse::<u16>() {
+///             Ok(port) if port > 1024 => Ok(Port(port)),
+///             _ => Err(format!("{value} is not an unprivileged port")),
+///         }
+///     }
+///
+///     fn type_name() -> &'static str {
+///         "port"
+///     }
+/// }
+///
+/// assert!(Port::parse("8080").is_ok());
+/// assert!(Port::parse("80").is_err());
+/// ```
+pub trait FliValue: Sized {
+    /// Parses the raw value passed on the command line
+    fn parse(value: &str) -> Result<Self, String>;
+
+    /// The name of the type, used in error messages
+    fn type_name() -> &'static str;
+
+    /// Values to suggest when completing the option, none by default
+    fn completion_hints() -> Vec<String> {
+        vec![]
+    }
+}
+
+impl FliValue for String {
+    fn parse(value: &str) -> Result<Self, String> {
+        Ok(value.to_string())
+    }
+
+    fn type_name() -> &'static str {
+        "string"
+    }
+}
+
+impl FliValue for bool {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value.to_ascii_lowercase().as_str() {
+            "true" | "yes" | "y" | "on" | "1" => Ok(true),
+            "false" | "no" | "n" | "off" | "0" => Ok(false),
+            _ => Err(format!("'{value}' is not a boolean")),
+        }
+    }
+
+    fn type_name() -> &'static str {
+        "boolean"
+    }
+
+    fn completion_hints() -> Vec<String> {
+        vec!["true".to_string(), "false".to_string()]
+    }
+}
+
+impl FliValue for PathBuf {
+    fn parse(value: &str) -> Result<Self, String> {
+        Ok(PathBuf::from(value))
+    }
+
+    fn type_name() -> &'static str {
+        "path"
+    }
+}
+
+impl FliValue for Duration {
+    fn parse(value: &str) -> Result<Self, String> {
+        parse_duration(value)
+            .ok_or_else(|| format!("'{value}' is not a duration like 30s, 5m or 2h30m"))
+    }
+
+    fn type_name() -> &'static str {
+        "duration"
+    }
+}
+
+/// A file to read from where `-` stands for the standard input, like `cat -`
+///
+/// # Example
+/// ```
+/// use fli::{FliValue, Input};
+///
+/// assert!(Input::parse("-").unwrap().is_stdin());
+/// assert_eq!(Input::parse("notes.txt").unwrap(), Input::Path("notes.txt".into()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    /// `-` was passed
+    Stdin,
+    /// The path of the file
+    Path(PathBuf),
+}
+
+impl Input {
+    /// Checks if the input is the standard input
+    pub fn is_stdin(&self) -> bool {
+        matches!(self, Input::Stdin)
+    }
+
+    /// Opens the file or locks the standard input for reading
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            Input::Stdin => Ok(Box::new(io::stdin().lock())),
+            Input::Path(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+        }
+    }
+}
+
+impl FliValue for Input {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "-" => Ok(Input::Stdin),
+            path => Ok(Input::Path(PathBuf::from(path))),
+        }
+    }
+
+    fn type_name() -> &'static str {
+        "file"
+    }
+}
+
+macro_rules! impl_fli_value_from_str {
+    ($($ty:ty => $name:expr),* $(,)?) => {
+        $(
+            impl FliValue for $ty {
+                fn parse(value: &str) -> Result<Self, String> {
+                    value
+                        .parse::<$ty>()
+                        .map_err(|_| format!("'{value}' is not a valid {}", $name))
+                }
+
+                fn type_name() -> &'static str {
+                    $name
+                }
+            }
+        )*
+    };
+}
+
+impl_fli_value_from_str!(
+    i8 => "integer",
+    i16 => "integer",
+    i32 => "integer",
+    i64 => "integer",
+    isize => "integer",
+    u8 => "unsigned integer",
+    u16 => "unsigned integer",
+    u32 => "unsigned integer",
+    u64 => "unsigned integer",
+    usize => "unsigned integer",
+    f32 => "number",
+    f64 => "number",
+    char => "character",
+);